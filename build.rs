@@ -1,6 +1,34 @@
 use std::{env, path::PathBuf};
 
+// Version of `package` resolved in Cargo.lock
+fn locked_version(package: &str) -> Option<String> {
+    let lock = std::fs::read_to_string("Cargo.lock").ok()?;
+    let name = format!("name = \"{package}\"");
+    let mut lines = lock.lines().skip_while(|line| *line != name).skip(1);
+    let version = lines.next()?.strip_prefix("version = \"")?;
+    Some(version.trim_end_matches('"').to_owned())
+}
+
 fn main() {
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    if let Some(version) = locked_version("dotlottie-rs") {
+        println!("cargo:rustc-env=L0TTIE_DOTLOTTIE_VERSION={version}");
+    }
+
+    if env::var("CARGO_FEATURE_EMBEDDED_ANIMATION").is_ok() {
+        println!("cargo:rerun-if-env-changed=L0TTIE_EMBEDDED_ANIMATION");
+        let path = env::var("L0TTIE_EMBEDDED_ANIMATION").expect(
+            "The embedded_animation feature requires L0TTIE_EMBEDDED_ANIMATION set to a Lottie JSON path",
+        );
+        // Relative to the crate root, include_str! would resolve it relative to src/
+        let path = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join(path);
+        println!("cargo:rerun-if-changed={}", path.display());
+        println!(
+            "cargo:rustc-env=L0TTIE_EMBEDDED_ANIMATION_PATH={}",
+            path.display()
+        );
+    }
+
     if env::var("CARGO_CFG_WINDOWS").is_ok() {
         return;
     }
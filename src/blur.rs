@@ -0,0 +1,102 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::pixel;
+
+// Blur one line of `len` pixels starting at `start`, `stride` apart,
+// averaging over a window of `radius` pixels on each side clamped to the edges.
+fn blur_line(
+    frame: &mut [u32],
+    line: &mut Vec<[u8; 4]>,
+    start: usize,
+    stride: usize,
+    len: usize,
+    radius: usize,
+) {
+    line.clear();
+    line.extend((0..len).map(|i| pixel::unpack(frame[start + i * stride])));
+    let window = (2 * radius + 1) as u32;
+    let at = |i: isize| line[i.clamp(0, len as isize - 1) as usize];
+    let mut sums = [0u32; 4];
+    for i in -(radius as isize)..=radius as isize {
+        for (sum, channel) in sums.iter_mut().zip(at(i)) {
+            *sum += channel as u32;
+        }
+    }
+    for i in 0..len {
+        frame[start + i * stride] = pixel::pack(std::array::from_fn(|c| {
+            ((sums[c] + window / 2) / window) as u8
+        }));
+        let (remove, add) = (
+            at(i as isize - radius as isize),
+            at((i + radius + 1) as isize),
+        );
+        for c in 0..4 {
+            sums[c] = sums[c] + add[c] as u32 - remove[c] as u32;
+        }
+    }
+}
+
+/// Box blur premultiplied `frame` horizontally then vertically,
+/// `line` is a reusable scratch buffer.
+pub(crate) fn box_blur(
+    frame: &mut [u32],
+    width: usize,
+    height: usize,
+    radius: usize,
+    line: &mut Vec<[u8; 4]>,
+) {
+    if radius == 0 || width == 0 || height == 0 {
+        return;
+    }
+    for y in 0..height {
+        blur_line(frame, line, y * width, 1, width, radius);
+    }
+    for x in 0..width {
+        blur_line(frame, line, x, width, height, radius);
+    }
+}
+
+/// Composite premultiplied `front` over `back`, writing the result into `front`.
+pub(crate) fn over(front: &mut [u32], back: &[u32]) {
+    for (front, back) in front.iter_mut().zip(back) {
+        let f = pixel::unpack(*front);
+        if f[3] == 255 {
+            continue;
+        }
+        let b = pixel::unpack(*back);
+        let inverse = 255 - f[3] as u32;
+        *front = pixel::pack(std::array::from_fn(|c| {
+            (f[c] as u32 + (b[c] as u32 * inverse + 127) / 255).min(255) as u8
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_box_blur_uniform() {
+        let color = pixel::pack([10, 20, 30, 255]);
+        let mut frame = [color; 12];
+        box_blur(&mut frame, 4, 3, 2, &mut Vec::new());
+        assert_eq!(frame, [color; 12]);
+    }
+
+    #[test]
+    fn test_box_blur_spreads() {
+        let mut frame = [0, 0, pixel::pack([255, 255, 255, 255]), 0, 0];
+        box_blur(&mut frame, 5, 1, 1, &mut Vec::new());
+        assert_eq!(frame.map(|p| pixel::unpack(p)[3]), [0, 85, 85, 85, 0]);
+    }
+
+    #[test]
+    fn test_over() {
+        let back = [pixel::pack([0, 0, 200, 255]), pixel::pack([0, 0, 200, 255])];
+        let mut front = [pixel::pack([100, 0, 0, 255]), pixel::pack([50, 0, 0, 128])];
+        over(&mut front, &back);
+        assert_eq!(pixel::unpack(front[0]), [100, 0, 0, 255]);
+        assert_eq!(pixel::unpack(front[1]), [50, 0, 100, 255]);
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// 3x5 glyphs, one row per byte with the leftmost pixel in bit 2
+const GLYPH_WIDTH: usize = 3;
+const GLYPH_HEIGHT: usize = 5;
+const SCALE: usize = 2;
+const MARGIN: usize = 2;
+
+// Opaque ABGR8888
+const WHITE: u32 = 0xffffffff;
+const BLACK: u32 = 0xff000000;
+
+fn glyph(c: char) -> [u8; GLYPH_HEIGHT] {
+    match c {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        _ => [0; GLYPH_HEIGHT],
+    }
+}
+
+/// Draw `text` in white on a black box in the top left corner of `frame`,
+/// clipped to the frame. Unsupported characters are drawn as spaces.
+pub(crate) fn draw(frame: &mut [u32], width: usize, height: usize, text: &str) {
+    if width == 0 {
+        return;
+    }
+    let advance = (GLYPH_WIDTH + 1) * SCALE;
+    let box_width = (MARGIN * 2 + text.chars().count() * advance).min(width);
+    let box_height = (MARGIN * 2 + GLYPH_HEIGHT * SCALE).min(height);
+    for row in frame.chunks_exact_mut(width).take(box_height) {
+        row[..box_width].fill(BLACK);
+    }
+    for (i, c) in text.chars().enumerate() {
+        let rows = glyph(c);
+        for y in 0..GLYPH_HEIGHT * SCALE {
+            for x in 0..GLYPH_WIDTH * SCALE {
+                let (px, py) = (MARGIN + i * advance + x, MARGIN + y);
+                if px >= box_width || py >= box_height {
+                    continue;
+                }
+                if rows[y / SCALE] & (1 << (GLYPH_WIDTH - 1 - x / SCALE)) != 0 {
+                    frame[py * width + px] = WHITE;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pixel;
+
+    #[test]
+    fn test_draw() {
+        let (width, height) = (32, 16);
+        let mut frame = vec![0; width * height];
+        draw(&mut frame, width, height, "1");
+        // Box covers the margin, glyph pixels are white
+        assert_eq!(frame[0], BLACK);
+        assert_eq!(frame[MARGIN * width + MARGIN], BLACK);
+        assert_eq!(frame[MARGIN * width + MARGIN + SCALE], WHITE);
+        // Outside the box is untouched
+        assert_eq!(frame[width - 1], 0);
+        assert_eq!(frame[(height - 1) * width], 0);
+    }
+
+    #[test]
+    fn test_draw_clipped() {
+        let mut frame = vec![0; 4 * 2];
+        draw(&mut frame, 4, 2, "123456");
+        assert!(frame.iter().all(|p| pixel::unpack(*p)[3] == 255));
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::VecDeque;
+
+/// Least recently used cache of rendered frames, most recent last.
+#[derive(Debug)]
+pub(crate) struct FrameCache<K> {
+    capacity: usize,
+    entries: VecDeque<(K, Vec<u32>)>,
+}
+
+impl<K: PartialEq> FrameCache<K> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        FrameCache {
+            capacity,
+            entries: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.entries.len() > capacity {
+            self.entries.pop_front();
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub(crate) fn get(&mut self, key: &K) -> Option<&[u32]> {
+        let index = self.entries.iter().position(|(k, _)| k == key)?;
+        let entry = self.entries.remove(index)?;
+        self.entries.push_back(entry);
+        self.entries.back().map(|(_, frame)| frame.as_slice())
+    }
+
+    /// Insert a copy of `frame`, reusing the buffer of the evicted entry if full.
+    pub(crate) fn insert(&mut self, key: K, frame: &[u32]) {
+        if self.capacity == 0 {
+            return;
+        }
+        if let Some(index) = self.entries.iter().position(|(k, _)| *k == key) {
+            self.entries.remove(index);
+        }
+        let mut buffer = if self.entries.len() >= self.capacity {
+            self.entries
+                .pop_front()
+                .map(|(_, buffer)| buffer)
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        buffer.clear();
+        buffer.extend_from_slice(frame);
+        self.entries.push_back((key, buffer));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = FrameCache::new(2);
+        cache.insert(1, &[1]);
+        cache.insert(2, &[2]);
+        assert_eq!(cache.get(&1), Some([1].as_slice()));
+        cache.insert(3, &[3]);
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.get(&1), Some([1].as_slice()));
+        assert_eq!(cache.get(&3), Some([3].as_slice()));
+    }
+
+    #[test]
+    fn test_replace_existing() {
+        let mut cache = FrameCache::new(2);
+        cache.insert(1, &[1]);
+        cache.insert(1, &[10]);
+        cache.insert(2, &[2]);
+        assert_eq!(cache.get(&1), Some([10].as_slice()));
+        assert_eq!(cache.get(&2), Some([2].as_slice()));
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut cache = FrameCache::new(0);
+        cache.insert(1, &[1]);
+        assert_eq!(cache.get(&1), None);
+
+        cache.set_capacity(3);
+        for key in 0..3 {
+            cache.insert(key, &[key]);
+        }
+        cache.set_capacity(1);
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.get(&2), Some([2].as_slice()));
+    }
+}
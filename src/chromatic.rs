@@ -0,0 +1,30 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::pixel;
+
+/// Compose the red, green and blue channels of three renders into `outframe`.
+pub(crate) fn compose(red: &[u32], green: &[u32], blue: &[u32], outframe: &mut [u32]) {
+    for (((out, r), g), b) in outframe.iter_mut().zip(red).zip(green).zip(blue) {
+        let [r, _, _, ra] = pixel::unpack(*r);
+        let [_, g, _, ga] = pixel::unpack(*g);
+        let [_, _, b, ba] = pixel::unpack(*b);
+        // Premultiplied channels are each bounded by their own alpha
+        *out = pixel::pack([r, g, b, ra.max(ga).max(ba)]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compose() {
+        let red = [pixel::pack([10, 20, 30, 40])];
+        let green = [pixel::pack([50, 60, 70, 80])];
+        let blue = [pixel::pack([90, 100, 110, 120])];
+        let mut out = [0];
+        compose(&red, &green, &blue, &mut out);
+        assert_eq!(pixel::unpack(out[0]), [10, 60, 110, 120]);
+    }
+}
@@ -0,0 +1,349 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::pixel;
+
+/// Multiply RGB by `color`, blended with identity by `strength` (0..1).
+pub(crate) fn tint(frame: &mut [u32], color: [f32; 3], strength: f32) {
+    let strength = strength.clamp(0.0, 1.0);
+    let factors = color.map(|c| 1.0 - strength + strength * c.clamp(0.0, 1.0));
+    for pixel in frame.iter_mut() {
+        let [r, g, b, a] = pixel::unpack(*pixel);
+        let scale = |c: u8, factor: f32| (c as f32 * factor).round() as u8;
+        *pixel = pixel::pack([
+            scale(r, factors[0]),
+            scale(g, factors[1]),
+            scale(b, factors[2]),
+            a,
+        ]);
+    }
+}
+
+// Rec. 709 luma weights, matching sRGB primaries
+const LUMA: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+/// Scale saturation, 0 is grayscale and 1 leaves the frame unchanged.
+pub(crate) fn saturate(frame: &mut [u32], saturation: f32) {
+    let saturation = saturation.max(0.0);
+    for pixel in frame.iter_mut() {
+        let [r, g, b, a] = pixel::unpack(*pixel);
+        let luma = LUMA[0] * r as f32 + LUMA[1] * g as f32 + LUMA[2] * b as f32;
+        // Premultiplied color must not exceed alpha
+        let scale = |c: u8| {
+            (luma + (c as f32 - luma) * saturation)
+                .round()
+                .clamp(0.0, a as f32) as u8
+        };
+        *pixel = pixel::pack([scale(r), scale(g), scale(b), a]);
+    }
+}
+
+/// Row major 3x4 color matrix, each row has 3 channel weights and an offset.
+pub(crate) type Matrix = [f32; 12];
+
+/// Parse 9 (3x3) or 12 (3x4 with offsets) comma separated floats.
+pub(crate) fn parse_matrix(value: &str) -> anyhow::Result<Matrix> {
+    let values = value
+        .split(',')
+        .map(|v| v.trim().parse::<f32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| anyhow::anyhow!("Invalid color matrix value: {err}"))?;
+    let columns = match values.len() {
+        9 => 3,
+        12 => 4,
+        count => anyhow::bail!("Color matrix needs 9 or 12 values, got {count}"),
+    };
+    let mut matrix = [0.0; 12];
+    for (row, chunk) in values.chunks_exact(columns).enumerate() {
+        matrix[row * 4..row * 4 + columns].copy_from_slice(chunk);
+    }
+    Ok(matrix)
+}
+
+/// Transform RGB by `matrix`, offsets are normalized (0..1) straight color.
+pub(crate) fn apply_matrix(frame: &mut [u32], matrix: &Matrix) {
+    for pixel in frame.iter_mut() {
+        let [r, g, b, a] = pixel::unpack(*pixel);
+        if a == 0 {
+            continue;
+        }
+        let rgb = [r, g, b].map(|c| c as f32);
+        // Linear in premultiplied color, scale offsets by alpha to match
+        let channel = |row: &[f32]| {
+            (row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2] + row[3] * a as f32)
+                .round()
+                .clamp(0.0, a as f32) as u8
+        };
+        *pixel = pixel::pack([
+            channel(&matrix[0..4]),
+            channel(&matrix[4..8]),
+            channel(&matrix[8..12]),
+            a,
+        ]);
+    }
+}
+
+pub(crate) type Lut = [u8; 256];
+
+/// Raise normalized channel values to the power `gamma`.
+pub(crate) fn gamma_lut(gamma: f32) -> Lut {
+    std::array::from_fn(|i| ((i as f32 / 255.0).powf(gamma) * 255.0).round() as u8)
+}
+
+/// Multiply channel values by `2^stops`, clamped.
+pub(crate) fn exposure_lut(stops: f32) -> Lut {
+    let gain = stops.exp2();
+    std::array::from_fn(|i| (i as f32 * gain).round().min(255.0) as u8)
+}
+
+/// Map straight (unpremultiplied) RGB through `lut`, preserving alpha.
+pub(crate) fn apply_lut(frame: &mut [u32], lut: &Lut) {
+    for pixel in frame.iter_mut() {
+        let [r, g, b, a] = pixel::unpack(*pixel);
+        let map = |c: u8| match a {
+            0 => c,
+            255 => lut[c as usize],
+            _ => {
+                let straight = (c as u32 * 255 / a as u32).min(255);
+                (lut[straight as usize] as u32 * a as u32 / 255) as u8
+            }
+        };
+        *pixel = pixel::pack([map(r), map(g), map(b), a]);
+    }
+}
+
+/// Make alpha binary, opaque where it is at least `threshold` (0..1) and transparent elsewhere.
+pub(crate) fn threshold_alpha(frame: &mut [u32], threshold: f32) {
+    let threshold = (threshold.clamp(0.0, 1.0) * 255.0).round() as u8;
+    for pixel in frame.iter_mut() {
+        let [r, g, b, a] = pixel::unpack(*pixel);
+        *pixel = if a == 0 || a < threshold {
+            0
+        } else {
+            // Unpremultiply to the now opaque alpha
+            let straight = |c: u8| (c as u32 * 255 / a as u32).min(255) as u8;
+            pixel::pack([straight(r), straight(g), straight(b), 255])
+        };
+    }
+}
+
+/// Replace alpha with `255 - alpha`, re-premultiplying the straight color.
+/// Fully transparent pixels have no color and become opaque black.
+pub(crate) fn invert_alpha(frame: &mut [u32]) {
+    for pixel in frame.iter_mut() {
+        let [r, g, b, a] = pixel::unpack(*pixel);
+        let inverse = 255 - a as u32;
+        let scale = |c: u8| {
+            if a == 0 {
+                0
+            } else {
+                ((c as u32 * inverse + a as u32 / 2) / a as u32).min(inverse) as u8
+            }
+        };
+        *pixel = pixel::pack([scale(r), scale(g), scale(b), inverse as u8]);
+    }
+}
+
+/// Scale premultiplied `frame` by `opacity` (0-1).
+pub(crate) fn fade(frame: &mut [u32], opacity: f32) {
+    for pixel in frame.iter_mut() {
+        let rgba = pixel::unpack(*pixel);
+        *pixel = pixel::pack(rgba.map(|c| (c as f32 * opacity).round() as u8));
+    }
+}
+
+/// Straight color of a premultiplied `pixel` made opaque, None if transparent.
+pub(crate) fn opaque_color(pixel: u32) -> Option<[u8; 4]> {
+    let [r, g, b, a] = pixel::unpack(pixel);
+    let straight = |c: u8| ((c as u32 * 255 + a as u32 / 2) / a as u32).min(255) as u8;
+    (a > 0).then(|| [straight(r), straight(g), straight(b), 255])
+}
+
+/// Composite premultiplied `frame` over an opaque `matte` color.
+pub(crate) fn flatten(frame: &mut [u32], matte: [u8; 3]) {
+    for pixel in frame.iter_mut() {
+        let [r, g, b, a] = pixel::unpack(*pixel);
+        if a == 255 {
+            continue;
+        }
+        let inverse = 255 - a as u32;
+        let over = |c: u8, m: u8| (c as u32 + (m as u32 * inverse + 127) / 255).min(255) as u8;
+        *pixel = pixel::pack([over(r, matte[0]), over(g, matte[1]), over(b, matte[2]), 255]);
+    }
+}
+
+/// Parse `#RRGGBBAA` or `#RRGGBB` (opaque) hex colors.
+pub(crate) fn parse_hex_rgba(value: &str) -> Option<[u8; 4]> {
+    let hex = value.trim().strip_prefix('#')?;
+    if !matches!(hex.len(), 6 | 8) || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok();
+    let alpha = if hex.len() == 8 { channel(3)? } else { 255 };
+    Some([channel(0)?, channel(1)?, channel(2)?, alpha])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tint() {
+        let mut frame = [pixel::pack([200, 200, 200, 255])];
+        tint(&mut frame, [0.0, 0.5, 1.0], 1.0);
+        assert_eq!(pixel::unpack(frame[0]), [0, 100, 200, 255]);
+    }
+
+    #[test]
+    fn test_tint_strength() {
+        let mut frame = [pixel::pack([200, 200, 200, 128])];
+        tint(&mut frame, [0.0, 0.0, 0.0], 0.5);
+        assert_eq!(pixel::unpack(frame[0]), [100, 100, 100, 128]);
+    }
+
+    #[test]
+    fn test_gamma_lut() {
+        let lut = gamma_lut(2.2);
+        assert_eq!(lut[0], 0);
+        assert_eq!(lut[128], 56);
+        assert_eq!(lut[255], 255);
+        assert_eq!(gamma_lut(1.0), std::array::from_fn(|i| i as u8));
+    }
+
+    #[test]
+    fn test_exposure_lut() {
+        let lut = exposure_lut(1.0);
+        assert_eq!(lut[0], 0);
+        assert_eq!(lut[100], 200);
+        assert_eq!(lut[200], 255);
+        assert_eq!(exposure_lut(-1.0)[100], 50);
+        assert_eq!(exposure_lut(0.0), std::array::from_fn(|i| i as u8));
+    }
+
+    #[test]
+    fn test_apply_lut_premultiplied() {
+        let lut = gamma_lut(2.2);
+        let mut frame = [
+            pixel::pack([128, 128, 128, 255]),
+            pixel::pack([64, 64, 64, 128]),
+        ];
+        apply_lut(&mut frame, &lut);
+        assert_eq!(pixel::unpack(frame[0]), [56, 56, 56, 255]);
+        // Straight value 127 maps to 55, premultiplied by 128/255
+        assert_eq!(pixel::unpack(frame[1]), [27, 27, 27, 128]);
+    }
+
+    #[test]
+    fn test_saturate_grayscale() {
+        let mut frame = [pixel::pack([255, 0, 0, 255])];
+        saturate(&mut frame, 0.0);
+        assert_eq!(pixel::unpack(frame[0]), [54, 54, 54, 255]);
+    }
+
+    #[test]
+    fn test_saturate_identity() {
+        let mut frame = [
+            pixel::pack([255, 10, 0, 255]),
+            pixel::pack([20, 30, 40, 50]),
+        ];
+        let original = frame;
+        saturate(&mut frame, 1.0);
+        assert_eq!(frame, original);
+    }
+
+    #[test]
+    fn test_opaque_color() {
+        assert_eq!(
+            opaque_color(pixel::pack([50, 25, 0, 128])),
+            Some([100, 50, 0, 255])
+        );
+        assert_eq!(
+            opaque_color(pixel::pack([10, 20, 30, 255])),
+            Some([10, 20, 30, 255])
+        );
+        assert_eq!(opaque_color(0), None);
+    }
+
+    #[test]
+    fn test_fade() {
+        let mut frame = [pixel::pack([200, 100, 0, 255])];
+        fade(&mut frame, 0.5);
+        assert_eq!(pixel::unpack(frame[0]), [100, 50, 0, 128]);
+    }
+
+    #[test]
+    fn test_invert_alpha() {
+        let mut frame = [
+            pixel::pack([100, 50, 0, 200]),
+            pixel::pack([10, 20, 30, 255]),
+            0,
+        ];
+        invert_alpha(&mut frame);
+        // Straight color (127, 63, 0) premultiplied by 55
+        assert_eq!(pixel::unpack(frame[0]), [28, 14, 0, 55]);
+        assert_eq!(frame[1], 0);
+        assert_eq!(pixel::unpack(frame[2]), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_threshold_alpha() {
+        let mut frame = [
+            pixel::pack([100, 50, 0, 200]),
+            pixel::pack([100, 50, 0, 100]),
+            pixel::pack([10, 20, 30, 255]),
+        ];
+        threshold_alpha(&mut frame, 0.5);
+        assert_eq!(pixel::unpack(frame[0]), [127, 63, 0, 255]);
+        assert_eq!(frame[1], 0);
+        assert_eq!(pixel::unpack(frame[2]), [10, 20, 30, 255]);
+    }
+
+    #[test]
+    fn test_flatten() {
+        let mut frame = [
+            0,
+            pixel::pack([100, 0, 0, 128]),
+            pixel::pack([10, 20, 30, 255]),
+        ];
+        flatten(&mut frame, [0, 0, 255]);
+        assert_eq!(pixel::unpack(frame[0]), [0, 0, 255, 255]);
+        assert_eq!(pixel::unpack(frame[1]), [100, 0, 127, 255]);
+        assert_eq!(pixel::unpack(frame[2]), [10, 20, 30, 255]);
+    }
+
+    #[test]
+    fn test_parse_hex_rgba() {
+        assert_eq!(parse_hex_rgba("#ff800040"), Some([255, 128, 0, 64]));
+        assert_eq!(parse_hex_rgba(" #FF8000 "), Some([255, 128, 0, 255]));
+        assert_eq!(parse_hex_rgba("ff800040"), None);
+        assert_eq!(parse_hex_rgba("#ff8000g0"), None);
+        assert_eq!(parse_hex_rgba("#ff80"), None);
+        assert_eq!(parse_hex_rgba(""), None);
+    }
+
+    #[test]
+    fn test_parse_matrix() {
+        let matrix = parse_matrix("0,0,1, 0,1,0, 1,0,0").unwrap();
+        assert_eq!(matrix, [0., 0., 1., 0., 0., 1., 0., 0., 1., 0., 0., 0.]);
+        let matrix = parse_matrix("1,0,0,0.5, 0,1,0,0, 0,0,1,0").unwrap();
+        assert_eq!(matrix[3], 0.5);
+        assert!(parse_matrix("1,0,0").is_err());
+        assert!(parse_matrix("1,0,0,0,1,0,0,0,x").is_err());
+    }
+
+    #[test]
+    fn test_apply_matrix() {
+        // Swap red and blue
+        let swap = parse_matrix("0,0,1,0,1,0,1,0,0").unwrap();
+        let mut frame = [pixel::pack([10, 20, 30, 255]), 0];
+        apply_matrix(&mut frame, &swap);
+        assert_eq!(pixel::unpack(frame[0]), [30, 20, 10, 255]);
+        assert_eq!(frame[1], 0);
+
+        // Offsets scale with alpha and clamp to it
+        let offset = parse_matrix("1,0,0,0.5, 0,1,0,0, 0,0,1,1").unwrap();
+        let mut frame = [pixel::pack([10, 20, 30, 128])];
+        apply_matrix(&mut frame, &offset);
+        assert_eq!(pixel::unpack(frame[0]), [74, 20, 128, 128]);
+    }
+}
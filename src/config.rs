@@ -0,0 +1,195 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::ffi::CStr;
+
+use anyhow::{Context, anyhow, bail};
+use frei0r_rs2::{Color, ParamInfo, ParamKind, Position};
+use serde_json::{Map, Value};
+
+/// Reported by unset optional color params, which setting it unsets.
+/// Unset colors are written as null.
+pub(crate) const UNSET_COLOR: Color = Color {
+    r: -1.0,
+    g: -1.0,
+    b: -1.0,
+};
+
+/// Optional color param `value`, None if any component is negative.
+pub(crate) fn optional_color(value: &Color) -> Option<Color> {
+    (value.r >= 0.0 && value.g >= 0.0 && value.b >= 0.0).then_some(*value)
+}
+
+fn name<P>(param: &ParamInfo<P>) -> &str {
+    param.name.to_str().unwrap_or_default()
+}
+
+// Params that can be copied, excluding those named in `read_only`
+fn writable<'a, P>(
+    params: &'a [ParamInfo<P>],
+    read_only: &'a [&CStr],
+) -> impl Iterator<Item = &'a ParamInfo<P>> {
+    params
+        .iter()
+        .filter(move |param| !read_only.contains(&param.name))
+}
+
+fn value<P>(plugin: &P, kind: &ParamKind<P>) -> Value {
+    match kind {
+        ParamKind::Bool { get, .. } => Value::Bool(get(plugin)),
+        ParamKind::Double { get, .. } => get(plugin).into(),
+        ParamKind::Color { get, .. } => match optional_color(&get(plugin)) {
+            Some(color) => vec![color.r, color.g, color.b].into(),
+            None => Value::Null,
+        },
+        ParamKind::Position { get, .. } => {
+            let position = get(plugin);
+            vec![position.x, position.y].into()
+        }
+        ParamKind::String { get, .. } => get(plugin).to_string_lossy().into_owned().into(),
+    }
+}
+
+/// Serialize `params` of `plugin` not named in `read_only` as a JSON object
+/// keyed by parameter name. Unset colors are null.
+pub(crate) fn to_string<P>(plugin: &P, params: &[ParamInfo<P>], read_only: &[&CStr]) -> String {
+    let config: Map<String, Value> = writable(params, read_only)
+        .map(|param| (name(param).to_string(), value(plugin, &param.kind)))
+        .collect();
+    Value::Object(config).to_string()
+}
+
+fn numbers<const N: usize>(value: &Value) -> Option<[f64; N]> {
+    let values = value.as_array().filter(|values| values.len() == N)?;
+    let mut numbers = [0.0; N];
+    for (number, value) in numbers.iter_mut().zip(values) {
+        *number = value.as_f64()?;
+    }
+    Some(numbers)
+}
+
+fn set<P>(plugin: &mut P, kind: &ParamKind<P>, value: &Value) -> Option<()> {
+    match kind {
+        ParamKind::Bool { set, .. } => set(plugin, value.as_bool()?),
+        ParamKind::Double { set, .. } => set(plugin, value.as_f64()?),
+        ParamKind::Color { set, .. } if value.is_null() => set(plugin, &UNSET_COLOR),
+        ParamKind::Color { set, .. } => {
+            let [r, g, b] = numbers(value)?.map(|c| c as f32);
+            set(plugin, &Color { r, g, b })
+        }
+        ParamKind::Position { set, .. } => {
+            let [x, y] = numbers(value)?;
+            set(plugin, &Position { x, y })
+        }
+        ParamKind::String { set, .. } => {
+            let value = std::ffi::CString::new(value.as_str()?).ok()?;
+            set(plugin, &value)
+        }
+    }
+    Some(())
+}
+
+/// Apply a `config` produced by `to_string` to `plugin`, in `params` order.
+/// Parameters missing from `config` are left unchanged.
+pub(crate) fn apply<P>(
+    plugin: &mut P,
+    params: &[ParamInfo<P>],
+    read_only: &[&CStr],
+    config: &str,
+) -> anyhow::Result<()> {
+    let config: Value = serde_json::from_str(config).context("Invalid config JSON")?;
+    let config = config.as_object().context("Config must be a JSON object")?;
+    if let Some(key) = config
+        .keys()
+        .find(|key| !writable(params, read_only).any(|param| name(param) == *key))
+    {
+        bail!("Unknown config parameter: {key}");
+    }
+    for param in writable(params, read_only) {
+        if let Some(value) = config.get(name(param)) {
+            set(plugin, &param.kind, value)
+                .ok_or_else(|| anyhow!("Invalid config value for {}: {value}", name(param)))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Params {
+        flag: bool,
+        amount: f64,
+        color: Option<Color>,
+        label: std::ffi::CString,
+    }
+
+    const PARAMS: &[ParamInfo<Params>] = &[
+        ParamInfo::new_bool(c"flag", c"Flag", |p| p.flag, |p, v| p.flag = v),
+        ParamInfo::new_double(c"amount", c"Amount", |p| p.amount, |p, v| p.amount = v),
+        ParamInfo::new_color(
+            c"color",
+            c"Color",
+            |p| p.color.unwrap_or(UNSET_COLOR),
+            |p, v| p.color = optional_color(v),
+        ),
+        ParamInfo::new_string(
+            c"label",
+            c"Label",
+            |p| p.label.as_c_str(),
+            |p, v| p.label = v.into(),
+        ),
+        ParamInfo::new_double(c"count", c"Count", |_| 3.0, |_, _| {}),
+    ];
+    const READ_ONLY: &[&CStr] = &[c"count"];
+
+    #[test]
+    fn test_round_trip() {
+        let source = Params {
+            flag: true,
+            amount: 2.5,
+            color: Some(Color {
+                r: 1.0,
+                g: 0.5,
+                b: 0.0,
+            }),
+            label: c"hello".into(),
+        };
+        let config = to_string(&source, PARAMS, READ_ONLY);
+        assert!(config.contains(r#""amount":2.5"#));
+        assert!(config.contains(r#""label":"hello""#));
+        assert!(!config.contains("count"));
+        let mut target = Params::default();
+        apply(&mut target, PARAMS, READ_ONLY, &config).unwrap();
+        assert_eq!(to_string(&target, PARAMS, READ_ONLY), config);
+    }
+
+    #[test]
+    fn test_apply_errors() {
+        let mut target = Params::default();
+        assert!(apply(&mut target, PARAMS, READ_ONLY, "not json").is_err());
+        assert!(apply(&mut target, PARAMS, READ_ONLY, r#"{"count":1}"#).is_err());
+        assert!(apply(&mut target, PARAMS, READ_ONLY, r#"{"color":[1,2]}"#).is_err());
+        apply(&mut target, PARAMS, READ_ONLY, r#"{"amount":4}"#).unwrap();
+        assert_eq!(target.amount, 4.0);
+    }
+
+    #[test]
+    fn test_unset_color() {
+        let source = Params::default();
+        let config = to_string(&source, PARAMS, READ_ONLY);
+        assert!(config.contains(r#""color":null"#));
+        let mut target = Params {
+            color: Some(Color {
+                r: 0.0,
+                g: 0.0,
+                b: 0.0,
+            }),
+            ..Default::default()
+        };
+        apply(&mut target, PARAMS, READ_ONLY, &config).unwrap();
+        assert!(target.color.is_none());
+    }
+}
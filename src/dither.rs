@@ -0,0 +1,92 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::pixel;
+
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+// Offset in -1..=1 for a pixel, keyed only by its coordinates so the
+// pattern is identical every time a frame is rendered.
+fn offset(x: usize, y: usize) -> i16 {
+    let threshold = (BAYER[y % 4][x % 4] as f32 + 0.5) / 8.0 - 1.0;
+    threshold.round() as i16
+}
+
+pub(crate) fn apply(frame: &mut [u32], width: usize) {
+    if width == 0 {
+        return;
+    }
+    for (y, row) in frame.chunks_exact_mut(width).enumerate() {
+        for (x, pixel) in row.iter_mut().enumerate() {
+            let [r, g, b, a] = pixel::unpack(*pixel);
+            if a == 0 {
+                continue;
+            }
+            let offset = offset(x, y);
+            // Premultiplied color must not exceed alpha
+            let dither = |c: u8| (c as i16 + offset).clamp(0, a as i16) as u8;
+            *pixel = pixel::pack([dither(r), dither(g), dither(b), a]);
+        }
+    }
+}
+
+/// Widen a premultiplied 8-bit pixel at (x, y) to 16 bits per channel,
+/// dithering color within one 8-bit step to hide banding. Alpha is scaled exactly.
+pub(crate) fn upsample(rgba: [u8; 4], x: usize, y: usize) -> [u16; 4] {
+    // -128..=128 in 16-bit units, half an 8-bit step either way
+    let offset = ((BAYER[y % 4][x % 4] as i32 * 2 - 15) * 257) / 30;
+    let alpha = rgba[3] as i32 * 257;
+    let widen = |c: u8| (c as i32 * 257 + offset).clamp(0, alpha) as u16;
+    [widen(rgba[0]), widen(rgba[1]), widen(rgba[2]), alpha as u16]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic() {
+        let mut first = vec![pixel::pack([128, 64, 32, 255]); 64];
+        let mut second = first.clone();
+        apply(&mut first, 8);
+        apply(&mut second, 8);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_offset_range() {
+        let offsets: Vec<i16> = (0..16).map(|i| offset(i % 4, i / 4)).collect();
+        assert_eq!(offsets.iter().filter(|o| **o == -1).count(), 4);
+        assert_eq!(offsets.iter().filter(|o| **o == 0).count(), 8);
+        assert_eq!(offsets.iter().filter(|o| **o == 1).count(), 4);
+    }
+
+    #[test]
+    fn test_upsample() {
+        assert_eq!(upsample([0; 4], 1, 2), [0; 4]);
+        assert_eq!(upsample([255; 4], 0, 0)[3], 65535);
+        for i in 0..16 {
+            let [r, _, _, a] = upsample([100, 0, 0, 255], i % 4, i / 4);
+            assert!(r.abs_diff(100 * 257) <= 128, "{r}");
+            assert_eq!(a, 65535);
+        }
+    }
+
+    #[test]
+    fn test_preserves_transparent_and_alpha() {
+        let mut frame = vec![
+            0,
+            pixel::pack([10, 10, 10, 10]),
+            0,
+            pixel::pack([10, 10, 10, 10]),
+        ];
+        apply(&mut frame, 2);
+        assert_eq!(frame[0], 0);
+        assert_eq!(frame[2], 0);
+        for pixel in [frame[1], frame[3]] {
+            let [r, g, b, a] = pixel::unpack(pixel);
+            assert_eq!(a, 10);
+            assert!(r <= a && g <= a && b <= a);
+        }
+    }
+}
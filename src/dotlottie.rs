@@ -0,0 +1,116 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::io::{Cursor, Read};
+
+use anyhow::Context;
+use serde_json::Value;
+
+type Archive<'a> = zip::ZipArchive<Cursor<&'a [u8]>>;
+
+// dotLottie v1 stores animations under "animations/", v2 under "a/"
+const ANIMATION_DIRS: &[&str] = &["animations", "a"];
+
+fn read_entry(archive: &mut Archive, name: &str) -> anyhow::Result<String> {
+    let mut entry = archive
+        .by_name(name)
+        .with_context(|| format!("Missing dotLottie entry: {name}"))?;
+    let mut data = String::new();
+    entry
+        .read_to_string(&mut data)
+        .with_context(|| format!("Failed to read dotLottie entry: {name}"))?;
+    Ok(data)
+}
+
+pub(crate) struct DotLottie {
+    pub animation: String,
+    pub count: usize,
+}
+
+fn animation_ids(manifest: &Value) -> Vec<&str> {
+    manifest
+        .get("animations")
+        .and_then(Value::as_array)
+        .map(|animations| {
+            animations
+                .iter()
+                .filter_map(|animation| animation.get("id").and_then(Value::as_str))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// The animation at `index`, or the manifest's active animation if `index` is None.
+// Out of range indices select the first animation.
+fn select_id<'a>(manifest: &'a Value, ids: &[&'a str], index: Option<usize>) -> Option<&'a str> {
+    match index {
+        Some(index) => ids.get(index).or(ids.first()).copied(),
+        None => manifest
+            .get("activeAnimationId")
+            .or_else(|| manifest.pointer("/initial/animation"))
+            .and_then(Value::as_str)
+            .or(ids.first().copied()),
+    }
+}
+
+/// Load the JSON of the animation at `index` (or the active animation) in a dotLottie archive.
+pub(crate) fn load(data: &[u8], index: Option<usize>) -> anyhow::Result<DotLottie> {
+    let mut archive =
+        zip::ZipArchive::new(Cursor::new(data)).context("Invalid dotLottie archive")?;
+    let manifest: Value = serde_json::from_str(&read_entry(&mut archive, "manifest.json")?)
+        .context("Invalid dotLottie manifest")?;
+    let ids = animation_ids(&manifest);
+    let id = select_id(&manifest, &ids, index).context("dotLottie manifest has no animations")?;
+    for dir in ANIMATION_DIRS {
+        if let Ok(animation) = read_entry(&mut archive, &format!("{dir}/{id}.json")) {
+            return Ok(DotLottie {
+                animation,
+                count: ids.len(),
+            });
+        }
+    }
+    anyhow::bail!("Missing dotLottie animation: {id}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(json: &str) -> Value {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_select_active() {
+        let v1 = manifest(r#"{"activeAnimationId":"b","animations":[{"id":"a"},{"id":"b"}]}"#);
+        let ids = animation_ids(&v1);
+        assert_eq!(ids, ["a", "b"]);
+        assert_eq!(select_id(&v1, &ids, None), Some("b"));
+
+        let v2 = manifest(
+            r#"{"version":"2","initial":{"animation":"b"},"animations":[{"id":"a"},{"id":"b"}]}"#,
+        );
+        assert_eq!(select_id(&v2, &animation_ids(&v2), None), Some("b"));
+
+        let first = manifest(r#"{"animations":[{"id":"a"},{"id":"b"}]}"#);
+        assert_eq!(select_id(&first, &animation_ids(&first), None), Some("a"));
+    }
+
+    #[test]
+    fn test_select_index() {
+        let v1 = manifest(
+            r#"{"activeAnimationId":"b","animations":[{"id":"a"},{"id":"b"},{"id":"c"}]}"#,
+        );
+        let ids = animation_ids(&v1);
+        assert_eq!(select_id(&v1, &ids, Some(0)), Some("a"));
+        assert_eq!(select_id(&v1, &ids, Some(2)), Some("c"));
+        assert_eq!(select_id(&v1, &ids, Some(3)), Some("a"));
+    }
+
+    #[test]
+    fn test_no_animations() {
+        let empty = manifest(r#"{"animations":[]}"#);
+        assert_eq!(select_id(&empty, &animation_ids(&empty), None), None);
+        assert_eq!(select_id(&empty, &animation_ids(&empty), Some(0)), None);
+    }
+}
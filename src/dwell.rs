@@ -0,0 +1,41 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/// Playback time of `time` on a timeline paused for `dwell` seconds at each
+/// of the ascending `stops` (in playback seconds).
+pub(crate) fn remap(time: f64, stops: &[f64], dwell: f64) -> f64 {
+    let mut time = time;
+    for stop in stops {
+        if time < *stop {
+            break;
+        }
+        if time < stop + dwell {
+            return *stop;
+        }
+        time -= dwell;
+    }
+    time
+}
+
+/// Length of one pass of a `duration` timeline with a `dwell` pause at each of `stops`.
+pub(crate) fn extended_duration(duration: f64, stops: &[f64], dwell: f64) -> f64 {
+    duration + stops.len() as f64 * dwell
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remap() {
+        let stops = [1.0, 3.0];
+        assert_eq!(remap(0.5, &stops, 2.0), 0.5);
+        assert_eq!(remap(1.0, &stops, 2.0), 1.0);
+        assert_eq!(remap(2.9, &stops, 2.0), 1.0);
+        assert_eq!(remap(4.0, &stops, 2.0), 2.0);
+        assert_eq!(remap(5.5, &stops, 2.0), 3.0);
+        assert_eq!(remap(7.5, &stops, 2.0), 3.5);
+        assert_eq!(remap(7.5, &[], 2.0), 7.5);
+        assert_eq!(extended_duration(4.0, &stops, 2.0), 8.0);
+    }
+}
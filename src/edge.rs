@@ -0,0 +1,200 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::ffi::CStr;
+
+use crate::mode::Mode;
+
+/// What is displayed for times outside the mode's playback cycle.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Edge {
+    // Each mode's own behavior, governed by the loop parameter
+    None,
+    Clamp,
+    Loop,
+    HoldBlack,
+}
+
+impl Edge {
+    /// Animation time at `time`, or None if nothing should be displayed.
+    pub(crate) fn next_frame(
+        &self,
+        mode: Mode,
+        time: f64,
+        duration: f32,
+        loop_animation: bool,
+        bounce_hold: f32,
+        bounce_skip: f32,
+    ) -> Option<f32> {
+        let cycle_duration = mode.cycle_duration(duration, bounce_hold, bounce_skip) as f64;
+        match self {
+            Edge::None => {
+                Some(mode.next_frame(time, duration, loop_animation, bounce_hold, bounce_skip))
+            }
+            Edge::Clamp => Some(mode.next_frame(
+                time.clamp(0.0, cycle_duration.max(0.0)),
+                duration,
+                false,
+                bounce_hold,
+                bounce_skip,
+            )),
+            Edge::Loop => {
+                let time = if cycle_duration > 0.0 {
+                    time.rem_euclid(cycle_duration)
+                } else {
+                    0.0
+                };
+                Some(mode.next_frame(time, duration, true, bounce_hold, bounce_skip))
+            }
+            Edge::HoldBlack => (0.0..=cycle_duration)
+                .contains(&time)
+                .then(|| mode.next_frame(time, duration, false, bounce_hold, bounce_skip)),
+        }
+    }
+}
+
+pub(crate) const EDGE_NONE: &CStr = c"none";
+pub(crate) const EDGE_CLAMP: &CStr = c"clamp";
+pub(crate) const EDGE_LOOP: &CStr = c"loop";
+pub(crate) const EDGE_HOLD_BLACK: &CStr = c"hold-black";
+
+impl From<&CStr> for Edge {
+    fn from(value: &CStr) -> Self {
+        if value == EDGE_CLAMP {
+            Edge::Clamp
+        } else if value == EDGE_LOOP {
+            Edge::Loop
+        } else if value == EDGE_HOLD_BLACK {
+            Edge::HoldBlack
+        } else {
+            Edge::None
+        }
+    }
+}
+
+impl From<Edge> for &'static CStr {
+    fn from(edge: Edge) -> Self {
+        match edge {
+            Edge::None => EDGE_NONE,
+            Edge::Clamp => EDGE_CLAMP,
+            Edge::Loop => EDGE_LOOP,
+            Edge::HoldBlack => EDGE_HOLD_BLACK,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DURATION: f32 = 10.0;
+
+    #[test]
+    fn test_none() {
+        let edge = Edge::None;
+        assert_eq!(
+            edge.next_frame(Mode::Forward, 15.0, DURATION, true, 0.0, 0.0),
+            Some(5.0)
+        );
+        assert_eq!(
+            edge.next_frame(Mode::Forward, 15.0, DURATION, false, 0.0, 0.0),
+            Some(10.0)
+        );
+    }
+
+    #[test]
+    fn test_clamp() {
+        let edge = Edge::Clamp;
+        for loop_animation in [false, true] {
+            assert_eq!(
+                edge.next_frame(Mode::Forward, -5.0, DURATION, loop_animation, 0.0, 0.0),
+                Some(0.0)
+            );
+            assert_eq!(
+                edge.next_frame(Mode::Forward, 15.0, DURATION, loop_animation, 0.0, 0.0),
+                Some(10.0)
+            );
+            assert_eq!(
+                edge.next_frame(Mode::Reverse, -5.0, DURATION, loop_animation, 0.0, 0.0),
+                Some(10.0)
+            );
+            assert_eq!(
+                edge.next_frame(Mode::Reverse, 15.0, DURATION, loop_animation, 0.0, 0.0),
+                Some(0.0)
+            );
+            assert_eq!(
+                edge.next_frame(Mode::Bounce, -5.0, DURATION, loop_animation, 0.0, 0.0),
+                Some(0.0)
+            );
+            assert_eq!(
+                edge.next_frame(Mode::Bounce, 25.0, DURATION, loop_animation, 0.0, 0.0),
+                Some(0.0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_loop() {
+        let edge = Edge::Loop;
+        for loop_animation in [false, true] {
+            assert_eq!(
+                edge.next_frame(Mode::Forward, -2.0, DURATION, loop_animation, 0.0, 0.0),
+                Some(8.0)
+            );
+            assert_eq!(
+                edge.next_frame(Mode::Forward, 15.0, DURATION, loop_animation, 0.0, 0.0),
+                Some(5.0)
+            );
+            assert_eq!(
+                edge.next_frame(Mode::Reverse, -2.0, DURATION, loop_animation, 0.0, 0.0),
+                Some(2.0)
+            );
+            assert_eq!(
+                edge.next_frame(Mode::Bounce, -5.0, DURATION, loop_animation, 0.0, 0.0),
+                Some(5.0)
+            );
+            assert_eq!(
+                edge.next_frame(Mode::Bounce, 25.0, DURATION, loop_animation, 0.0, 0.0),
+                Some(5.0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_hold_black() {
+        let edge = Edge::HoldBlack;
+        assert_eq!(
+            edge.next_frame(Mode::Forward, -0.5, DURATION, true, 0.0, 0.0),
+            None
+        );
+        assert_eq!(
+            edge.next_frame(Mode::Forward, 5.0, DURATION, true, 0.0, 0.0),
+            Some(5.0)
+        );
+        assert_eq!(
+            edge.next_frame(Mode::Forward, 10.0, DURATION, true, 0.0, 0.0),
+            Some(10.0)
+        );
+        assert_eq!(
+            edge.next_frame(Mode::Forward, 10.5, DURATION, true, 0.0, 0.0),
+            None
+        );
+        assert_eq!(
+            edge.next_frame(Mode::ReverseBounce, 15.0, DURATION, false, 0.0, 0.0),
+            Some(5.0)
+        );
+        assert_eq!(
+            edge.next_frame(Mode::ReverseBounce, 21.0, DURATION, false, 0.0, 0.0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_edge_from_cstr() {
+        assert_eq!(Edge::from(EDGE_NONE), Edge::None);
+        assert_eq!(Edge::from(EDGE_CLAMP), Edge::Clamp);
+        assert_eq!(Edge::from(EDGE_LOOP), Edge::Loop);
+        assert_eq!(Edge::from(EDGE_HOLD_BLACK), Edge::HoldBlack);
+        assert_eq!(Edge::from(c"unknown"), Edge::None);
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/// ThorVG renderer construction options.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub(crate) struct EngineConfig {
+    // 0 renders on the calling thread
+    pub threads: u32,
+}
+
+impl EngineConfig {
+    /// Parse `key=value;key2=value2` options, returning warnings for
+    /// entries that are invalid or not supported.
+    pub(crate) fn parse(spec: &str) -> (Self, Vec<String>) {
+        let mut config = EngineConfig::default();
+        let mut warnings = Vec::new();
+        for entry in spec.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            let Some((key, value)) = entry.split_once('=') else {
+                warnings.push(format!(
+                    "Invalid renderer_config entry, expected key=value: {entry}"
+                ));
+                continue;
+            };
+            match (key.trim(), value.trim()) {
+                ("engine", "sw") => {}
+                ("engine", engine) => {
+                    warnings.push(format!("Unsupported renderer engine, using sw: {engine}"))
+                }
+                ("threads", threads) => match threads.parse() {
+                    Ok(threads) => config.threads = threads,
+                    Err(_) => warnings.push(format!("Invalid renderer threads: {threads}")),
+                },
+                (key, _) => warnings.push(format!("Unknown renderer_config option: {key}")),
+            }
+        }
+        (config, warnings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let (config, warnings) = EngineConfig::parse("engine=sw; threads = 4;");
+        assert_eq!(config, EngineConfig { threads: 4 });
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_parse_warnings() {
+        let (config, warnings) = EngineConfig::parse("engine=gl;threads=x;quality=high;bogus");
+        assert_eq!(config, EngineConfig::default());
+        assert_eq!(warnings.len(), 4);
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::ffi::CStr;
+
+use crate::pixel;
+
+const PATTERN_SIZE: usize = 16;
+const PATTERN_COLORS: [u32; 2] = [0xff000000, 0xffff00ff];
+
+/// What to render when the animation is not loaded.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Fallback {
+    Transparent,
+    Background,
+    ErrorPattern,
+}
+
+impl Fallback {
+    pub(crate) fn fill(&self, frame: &mut [u32], width: usize, background: Option<[u8; 3]>) {
+        match (self, background) {
+            (Fallback::Background, Some([r, g, b])) => frame.fill(pixel::pack([r, g, b, 255])),
+            (Fallback::Transparent | Fallback::Background, _) => frame.fill(0),
+            (Fallback::ErrorPattern, _) => {
+                if width == 0 {
+                    return;
+                }
+                // Magenta and black checkerboard
+                for (y, row) in frame.chunks_exact_mut(width).enumerate() {
+                    for (x, pixel) in row.iter_mut().enumerate() {
+                        *pixel = PATTERN_COLORS[(x / PATTERN_SIZE + y / PATTERN_SIZE) % 2];
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub(crate) const FALLBACK_TRANSPARENT: &CStr = c"transparent";
+pub(crate) const FALLBACK_BACKGROUND: &CStr = c"background";
+pub(crate) const FALLBACK_ERROR_PATTERN: &CStr = c"error-pattern";
+
+impl From<&CStr> for Fallback {
+    fn from(value: &CStr) -> Self {
+        if value == FALLBACK_BACKGROUND {
+            Fallback::Background
+        } else if value == FALLBACK_ERROR_PATTERN {
+            Fallback::ErrorPattern
+        } else {
+            Fallback::Transparent
+        }
+    }
+}
+
+impl From<Fallback> for &'static CStr {
+    fn from(fallback: Fallback) -> Self {
+        match fallback {
+            Fallback::Transparent => FALLBACK_TRANSPARENT,
+            Fallback::Background => FALLBACK_BACKGROUND,
+            Fallback::ErrorPattern => FALLBACK_ERROR_PATTERN,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transparent() {
+        let mut frame = [1; 4];
+        Fallback::Transparent.fill(&mut frame, 2, Some([1, 2, 3]));
+        assert_eq!(frame, [0; 4]);
+    }
+
+    #[test]
+    fn test_background() {
+        let mut frame = [1; 4];
+        Fallback::Background.fill(&mut frame, 2, Some([1, 2, 3]));
+        assert_eq!(frame, [pixel::pack([1, 2, 3, 255]); 4]);
+        Fallback::Background.fill(&mut frame, 2, None);
+        assert_eq!(frame, [0; 4]);
+    }
+
+    #[test]
+    fn test_error_pattern() {
+        let width = PATTERN_SIZE * 2;
+        let mut frame = vec![0; width * width];
+        Fallback::ErrorPattern.fill(&mut frame, width, None);
+        assert_eq!(frame[0], PATTERN_COLORS[0]);
+        assert_eq!(frame[PATTERN_SIZE], PATTERN_COLORS[1]);
+        assert_eq!(frame[PATTERN_SIZE * width], PATTERN_COLORS[1]);
+        assert_eq!(
+            frame[PATTERN_SIZE * width + PATTERN_SIZE],
+            PATTERN_COLORS[0]
+        );
+    }
+
+    #[test]
+    fn test_fallback_from_cstr() {
+        assert_eq!(Fallback::from(FALLBACK_TRANSPARENT), Fallback::Transparent);
+        assert_eq!(Fallback::from(FALLBACK_BACKGROUND), Fallback::Background);
+        assert_eq!(
+            Fallback::from(FALLBACK_ERROR_PATTERN),
+            Fallback::ErrorPattern
+        );
+        assert_eq!(Fallback::from(c"unknown"), Fallback::Transparent);
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::ffi::CStr;
+
+/// Which field of an interlaced frame is displayed first.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum FieldOrder {
+    // Top field first, the even rows
+    Tff,
+    Bff,
+}
+
+impl FieldOrder {
+    /// Replace the rows of the second field in `frame` with those of `second`,
+    /// a frame rendered half a frame later.
+    pub(crate) fn weave(&self, frame: &mut [u32], second: &[u32], width: usize) {
+        if width == 0 {
+            return;
+        }
+        let skip = match self {
+            FieldOrder::Tff => 1,
+            FieldOrder::Bff => 0,
+        };
+        for (row, second_row) in frame
+            .chunks_exact_mut(width)
+            .zip(second.chunks_exact(width))
+            .skip(skip)
+            .step_by(2)
+        {
+            row.copy_from_slice(second_row);
+        }
+    }
+}
+
+pub(crate) const FIELD_ORDER_TFF: &CStr = c"tff";
+pub(crate) const FIELD_ORDER_BFF: &CStr = c"bff";
+
+impl From<&CStr> for FieldOrder {
+    fn from(value: &CStr) -> Self {
+        if value == FIELD_ORDER_BFF {
+            FieldOrder::Bff
+        } else {
+            FieldOrder::Tff
+        }
+    }
+}
+
+impl From<FieldOrder> for &'static CStr {
+    fn from(order: FieldOrder) -> Self {
+        match order {
+            FieldOrder::Tff => FIELD_ORDER_TFF,
+            FieldOrder::Bff => FIELD_ORDER_BFF,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weave_tff() {
+        let mut frame = [1, 1, 1, 1, 1, 1];
+        FieldOrder::Tff.weave(&mut frame, &[2; 6], 2);
+        assert_eq!(frame, [1, 1, 2, 2, 1, 1]);
+    }
+
+    #[test]
+    fn test_weave_bff() {
+        let mut frame = [1, 1, 1, 1, 1, 1];
+        FieldOrder::Bff.weave(&mut frame, &[2; 6], 2);
+        assert_eq!(frame, [2, 2, 1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn test_field_order_from_cstr() {
+        assert_eq!(FieldOrder::from(FIELD_ORDER_TFF), FieldOrder::Tff);
+        assert_eq!(FieldOrder::from(FIELD_ORDER_BFF), FieldOrder::Bff);
+        assert_eq!(FieldOrder::from(c"unknown"), FieldOrder::Tff);
+    }
+}
@@ -43,3 +43,327 @@ impl From<Fit> for &'static CStr {
         }
     }
 }
+
+/// Layout transform that keeps the animation at its authored size, centered.
+pub(crate) fn native_transform(
+    width: f32,
+    height: f32,
+    animation_width: f32,
+    animation_height: f32,
+) -> (f32, f32, f32, f32) {
+    (
+        animation_width,
+        animation_height,
+        (width - animation_width) / 2.0,
+        (height - animation_height) / 2.0,
+    )
+}
+
+/// Axis aligned `[x, y, width, height]` bounding the corner points of each
+/// layer bounds, None if empty.
+pub(crate) fn union_bounds(bounds: &[[f32; 8]]) -> Option<[f32; 4]> {
+    let points = bounds.iter().flat_map(|b| b.chunks_exact(2));
+    let (mut min, mut max) = ([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]);
+    for point in points {
+        for axis in 0..2 {
+            min[axis] = min[axis].min(point[axis]);
+            max[axis] = max[axis].max(point[axis]);
+        }
+    }
+    let (width, height) = (max[0] - min[0], max[1] - min[1]);
+    (width > 0.0 && height > 0.0).then_some([min[0], min[1], width, height])
+}
+
+/// Layout transform for the whole animation given `content` bounds within it,
+/// and the transform `content_layout` computed to fit the content size.
+pub(crate) fn content_transform(
+    content: [f32; 4],
+    animation_width: f32,
+    animation_height: f32,
+    content_layout: (f32, f32, f32, f32),
+) -> (f32, f32, f32, f32) {
+    let (width, height, tx, ty) = content_layout;
+    let (sx, sy) = (width / content[2], height / content[3]);
+    (
+        animation_width * sx,
+        animation_height * sy,
+        tx - content[0] * sx,
+        ty - content[1] * sy,
+    )
+}
+
+/// Map `rect` in animation coordinates through the layout `transform`
+/// of the whole animation to output pixels.
+pub(crate) fn map_rect(
+    rect: [f32; 4],
+    transform: (f32, f32, f32, f32),
+    animation_width: f32,
+    animation_height: f32,
+) -> [f32; 4] {
+    let (width, height, tx, ty) = transform;
+    let (sx, sy) = (width / animation_width, height / animation_height);
+    [
+        tx + rect[0] * sx,
+        ty + rect[1] * sy,
+        rect[2] * sx,
+        rect[3] * sy,
+    ]
+}
+
+/// Overlap of two `[x, y, width, height]` rects, None if they don't overlap.
+pub(crate) fn intersect(a: [f32; 4], b: [f32; 4]) -> Option<[f32; 4]> {
+    let (x, y) = (a[0].max(b[0]), a[1].max(b[1]));
+    let (right, bottom) = (
+        (a[0] + a[2]).min(b[0] + b[2]),
+        (a[1] + a[3]).min(b[1] + b[3]),
+    );
+    (right > x && bottom > y).then_some([x, y, right - x, bottom - y])
+}
+
+/// Top, bottom, left and right letterbox and pillarbox rects of `frame`
+/// outside `fitted`, empty ones omitted.
+pub(crate) fn bar_rects(fitted: [f32; 4], frame: [f32; 4]) -> Vec<[f32; 4]> {
+    let Some([x, y, w, h]) = intersect(fitted, frame) else {
+        return vec![frame];
+    };
+    let [fx, fy, fw, fh] = frame;
+    [
+        [fx, fy, fw, y - fy],
+        [fx, y + h, fw, fy + fh - (y + h)],
+        [fx, y, x - fx, h],
+        [x + w, y, fx + fw - (x + w), h],
+    ]
+    .into_iter()
+    .filter(|rect| rect[2] > 0.0 && rect[3] > 0.0)
+    .collect()
+}
+
+/// Top, bottom, left and right rects of a `border` wide frame just inside `rect`,
+/// None if `border` is not positive.
+pub(crate) fn border_rects(rect: [f32; 4], border: f32) -> Option<[[f32; 4]; 4]> {
+    if border <= 0.0 {
+        return None;
+    }
+    let [x, y, w, h] = rect;
+    let (bw, bh) = (border.min(w / 2.0), border.min(h / 2.0));
+    Some([
+        [x, y, w, bh],
+        [x, y + h - bh, w, bh],
+        [x, y + bh, bw, h - 2.0 * bh],
+        [x + w - bw, y + bh, bw, h - 2.0 * bh],
+    ])
+}
+
+/// Round a layout transform to whole pixel size and position.
+pub(crate) fn snap(transform: (f32, f32, f32, f32)) -> (f32, f32, f32, f32) {
+    let (width, height, tx, ty) = transform;
+    (
+        width.round().max(1.0),
+        height.round().max(1.0),
+        tx.round(),
+        ty.round(),
+    )
+}
+
+/// Edge to edge layout transform when the animation and frame aspect ratios
+/// match exactly, so every scaling fit covers the frame without rounding error.
+/// None if they differ or `fit` does not scale.
+pub(crate) fn exact_transform(
+    fit: dotlottie_rs::Fit,
+    size: [f32; 2],
+    animation: [f32; 2],
+) -> Option<(f32, f32, f32, f32)> {
+    let matches = size[0] * animation[1] == size[1] * animation[0];
+    (fit != dotlottie_rs::Fit::None && matches && animation[0] > 0.0 && animation[1] > 0.0)
+        .then_some((size[0], size[1], 0.0, 0.0))
+}
+
+// Horizontal and vertical scale `fit` applies to the animation
+fn scales(fit: dotlottie_rs::Fit, size: [f32; 2], animation: [f32; 2]) -> [f32; 2] {
+    let [sx, sy] = [size[0] / animation[0], size[1] / animation[1]];
+    match fit {
+        dotlottie_rs::Fit::Contain => [sx.min(sy); 2],
+        dotlottie_rs::Fit::Fill => [sx, sy],
+        dotlottie_rs::Fit::Cover => [sx.max(sy); 2],
+        dotlottie_rs::Fit::FitWidth => [sx; 2],
+        dotlottie_rs::Fit::FitHeight => [sy; 2],
+        dotlottie_rs::Fit::None => [1.0; 2],
+    }
+}
+
+/// Layout transform scaling each axis independently, horizontally as `fit_x`
+/// and vertically as `fit_y` would, positioned by `align`.
+pub(crate) fn per_axis_transform(
+    fit_x: dotlottie_rs::Fit,
+    fit_y: dotlottie_rs::Fit,
+    align: &[f32],
+    size: [f32; 2],
+    animation: [f32; 2],
+) -> (f32, f32, f32, f32) {
+    let width = animation[0] * scales(fit_x, size, animation)[0];
+    let height = animation[1] * scales(fit_y, size, animation)[1];
+    let align = |axis: usize| align.get(axis).copied().unwrap_or(0.5);
+    (
+        width,
+        height,
+        (size[0] - width) * align(0),
+        (size[1] - height) * align(1),
+    )
+}
+
+/// Alignment (0-1) panning back and forth across the overflow at `speed` alignment units per second.
+pub(crate) fn pan_align(time: f64, speed: f64) -> f32 {
+    let phase = (time * speed).rem_euclid(2.0);
+    (if phase <= 1.0 { phase } else { 2.0 - phase }) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_bounds() {
+        let bounds = [
+            [10.0, 20.0, 30.0, 20.0, 30.0, 40.0, 10.0, 40.0],
+            [25.0, 35.0, 50.0, 35.0, 50.0, 45.0, 25.0, 45.0],
+        ];
+        assert_eq!(union_bounds(&bounds), Some([10.0, 20.0, 40.0, 25.0]));
+        assert_eq!(union_bounds(&[[0.0; 8]]), None);
+        assert_eq!(union_bounds(&[]), None);
+    }
+
+    #[test]
+    fn test_content_transform() {
+        // 50x50 content at (25, 25) of a 100x100 animation, contained in 200x200
+        let transform = content_transform(
+            [25.0, 25.0, 50.0, 50.0],
+            100.0,
+            100.0,
+            (200.0, 200.0, 0.0, 0.0),
+        );
+        assert_eq!(transform, (400.0, 400.0, -100.0, -100.0));
+    }
+
+    #[test]
+    fn test_map_rect() {
+        let rect = map_rect(
+            [10.0, 20.0, 30.0, 40.0],
+            (200.0, 100.0, 5.0, 0.0),
+            100.0,
+            100.0,
+        );
+        assert_eq!(rect, [25.0, 20.0, 60.0, 40.0]);
+    }
+
+    #[test]
+    fn test_intersect() {
+        let frame = [0.0, 0.0, 100.0, 50.0];
+        assert_eq!(
+            intersect([-10.0, 10.0, 50.0, 100.0], frame),
+            Some([0.0, 10.0, 40.0, 40.0])
+        );
+        assert_eq!(intersect([100.0, 0.0, 10.0, 10.0], frame), None);
+    }
+
+    #[test]
+    fn test_bar_rects() {
+        let frame = [0.0, 0.0, 100.0, 50.0];
+        assert_eq!(
+            bar_rects([20.0, 0.0, 60.0, 50.0], frame),
+            [[0.0, 0.0, 20.0, 50.0], [80.0, 0.0, 20.0, 50.0]]
+        );
+        assert_eq!(
+            bar_rects([0.0, 10.0, 100.0, 30.0], frame),
+            [[0.0, 0.0, 100.0, 10.0], [0.0, 40.0, 100.0, 10.0]]
+        );
+        assert!(bar_rects([-10.0, -10.0, 120.0, 70.0], frame).is_empty());
+        assert_eq!(bar_rects([200.0, 0.0, 10.0, 10.0], frame), [frame]);
+    }
+
+    #[test]
+    fn test_border_rects() {
+        assert_eq!(
+            border_rects([0.0, 0.0, 10.0, 8.0], 2.0),
+            Some([
+                [0.0, 0.0, 10.0, 2.0],
+                [0.0, 6.0, 10.0, 2.0],
+                [0.0, 2.0, 2.0, 4.0],
+                [8.0, 2.0, 2.0, 4.0],
+            ])
+        );
+        assert_eq!(border_rects([0.0, 0.0, 10.0, 8.0], 0.0), None);
+    }
+
+    #[test]
+    fn test_snap() {
+        assert_eq!(snap((99.6, 50.2, 10.5, -0.4)), (100.0, 50.0, 11.0, 0.0));
+        assert_eq!(snap((0.2, 0.2, 0.0, 0.0)).0, 1.0);
+    }
+
+    #[test]
+    fn test_exact_transform() {
+        let (width, height, tx, ty) =
+            exact_transform(dotlottie_rs::Fit::Cover, [200.0, 200.0], [100.0, 100.0]).unwrap();
+        assert_eq!((width / 100.0, height / 100.0), (2.0, 2.0));
+        assert_eq!((tx, ty), (0.0, 0.0));
+        assert_eq!(
+            exact_transform(dotlottie_rs::Fit::Contain, [1920.0, 1080.0], [640.0, 360.0]),
+            Some((1920.0, 1080.0, 0.0, 0.0))
+        );
+        assert_eq!(
+            exact_transform(dotlottie_rs::Fit::Contain, [200.0, 100.0], [100.0, 100.0]),
+            None
+        );
+        assert_eq!(
+            exact_transform(dotlottie_rs::Fit::None, [200.0, 200.0], [100.0, 100.0]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_per_axis_transform() {
+        let transform = per_axis_transform(
+            dotlottie_rs::Fit::FitWidth,
+            dotlottie_rs::Fit::None,
+            &[0.5, 0.0],
+            [200.0, 100.0],
+            [100.0, 50.0],
+        );
+        assert_eq!(transform, (200.0, 50.0, 0.0, 0.0));
+        let transform = per_axis_transform(
+            dotlottie_rs::Fit::None,
+            dotlottie_rs::Fit::Fill,
+            &[0.5, 0.5],
+            [200.0, 100.0],
+            [100.0, 20.0],
+        );
+        assert_eq!(transform, (100.0, 100.0, 50.0, 0.0));
+    }
+
+    #[test]
+    fn test_pan_align() {
+        assert_eq!(pan_align(0.0, 0.5), 0.0);
+        assert_eq!(pan_align(1.0, 0.5), 0.5);
+        assert_eq!(pan_align(2.0, 0.5), 1.0);
+        assert_eq!(pan_align(3.0, 0.5), 0.5);
+        assert_eq!(pan_align(4.0, 0.5), 0.0);
+        assert_eq!(pan_align(-1.0, 0.5), 0.5);
+    }
+
+    #[test]
+    fn test_native_transform_smaller() {
+        assert_eq!(
+            native_transform(640.0, 360.0, 100.0, 50.0),
+            (100.0, 50.0, 270.0, 155.0)
+        );
+    }
+
+    #[test]
+    fn test_native_transform_larger() {
+        // Overflow is centered and clipped by the frame
+        assert_eq!(
+            native_transform(100.0, 100.0, 300.0, 200.0),
+            (300.0, 200.0, -100.0, -50.0)
+        );
+    }
+}
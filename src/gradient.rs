@@ -0,0 +1,133 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::ffi::CStr;
+
+use crate::pixel;
+
+/// Direction of the background gradient from the start to the end color.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Gradient {
+    None,
+    // Top to bottom
+    Vertical,
+    // Left to right
+    Horizontal,
+    // Center to corners
+    Radial,
+}
+
+impl Gradient {
+    /// Fill `frame` with the opaque gradient.
+    pub(crate) fn fill(
+        &self,
+        frame: &mut [u32],
+        width: usize,
+        height: usize,
+        start: [u8; 3],
+        end: [u8; 3],
+    ) {
+        if *self == Gradient::None || width == 0 {
+            return;
+        }
+        let (half_width, half_height) = (width as f32 / 2.0, height as f32 / 2.0);
+        let corner = half_width.hypot(half_height);
+        for (y, row) in frame.chunks_exact_mut(width).take(height).enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                let t = match self {
+                    Gradient::None => 0.0,
+                    Gradient::Vertical => py / height as f32,
+                    Gradient::Horizontal => px / width as f32,
+                    Gradient::Radial => (px - half_width).hypot(py - half_height) / corner,
+                };
+                let mix = |c: usize| {
+                    (start[c] as f32 + (end[c] as f32 - start[c] as f32) * t).round() as u8
+                };
+                *pixel = pixel::pack([mix(0), mix(1), mix(2), 255]);
+            }
+        }
+    }
+}
+
+pub(crate) const GRADIENT_NONE: &CStr = c"none";
+pub(crate) const GRADIENT_VERTICAL: &CStr = c"vertical";
+pub(crate) const GRADIENT_HORIZONTAL: &CStr = c"horizontal";
+pub(crate) const GRADIENT_RADIAL: &CStr = c"radial";
+
+impl From<&CStr> for Gradient {
+    fn from(value: &CStr) -> Self {
+        if value == GRADIENT_VERTICAL {
+            Gradient::Vertical
+        } else if value == GRADIENT_HORIZONTAL {
+            Gradient::Horizontal
+        } else if value == GRADIENT_RADIAL {
+            Gradient::Radial
+        } else {
+            Gradient::None
+        }
+    }
+}
+
+impl From<Gradient> for &'static CStr {
+    fn from(gradient: Gradient) -> Self {
+        match gradient {
+            Gradient::None => GRADIENT_NONE,
+            Gradient::Vertical => GRADIENT_VERTICAL,
+            Gradient::Horizontal => GRADIENT_HORIZONTAL,
+            Gradient::Radial => GRADIENT_RADIAL,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: [u8; 3] = [0, 0, 0];
+    const WHITE: [u8; 3] = [255, 255, 255];
+
+    #[test]
+    fn test_vertical() {
+        let mut frame = [0; 2 * 4];
+        Gradient::Vertical.fill(&mut frame, 2, 4, BLACK, WHITE);
+        let rows: Vec<_> = frame.chunks(2).map(|row| pixel::unpack(row[0])).collect();
+        assert_eq!(
+            rows,
+            [
+                [32, 32, 32, 255],
+                [96, 96, 96, 255],
+                [159, 159, 159, 255],
+                [223, 223, 223, 255]
+            ]
+        );
+        assert_eq!(frame[0], frame[1]);
+    }
+
+    #[test]
+    fn test_horizontal_and_radial() {
+        let mut frame = [0; 4 * 4];
+        Gradient::Horizontal.fill(&mut frame, 4, 4, BLACK, WHITE);
+        assert!(pixel::unpack(frame[0])[0] < pixel::unpack(frame[3])[0]);
+        assert_eq!(frame[0], frame[12]);
+
+        Gradient::Radial.fill(&mut frame, 4, 4, WHITE, BLACK);
+        // Brightest in the center, darkest in the corners
+        assert!(pixel::unpack(frame[5])[0] > pixel::unpack(frame[0])[0]);
+        assert_eq!(frame[0], frame[15]);
+    }
+
+    #[test]
+    fn test_none() {
+        let mut frame = [0; 4];
+        Gradient::None.fill(&mut frame, 2, 2, BLACK, WHITE);
+        assert_eq!(frame, [0; 4]);
+    }
+
+    #[test]
+    fn test_gradient_from_cstr() {
+        assert_eq!(Gradient::from(GRADIENT_RADIAL), Gradient::Radial);
+        assert_eq!(Gradient::from(GRADIENT_VERTICAL), Gradient::Vertical);
+        assert_eq!(Gradient::from(c"diagonal"), Gradient::None);
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::pixel;
+
+// Action safe and title safe rect sizes as a fraction of the frame
+const SAFE_AREAS: [f32; 2] = [0.9, 0.8];
+// Half length of the center cross as a fraction of the smaller dimension
+const CROSS: f32 = 0.05;
+// Guide line opacity
+const ALPHA: u32 = 128;
+// Opaque magenta ABGR8888 outline, rarely used by animations
+const OUTLINE: u32 = 0xffff00ff;
+
+// Blend white over the premultiplied pixel at (x, y)
+fn plot(frame: &mut [u32], width: usize, x: usize, y: usize) {
+    let rgba = pixel::unpack(frame[y * width + x]);
+    frame[y * width + x] =
+        pixel::pack(rgba.map(|c| ((ALPHA * 255 + c as u32 * (255 - ALPHA) + 127) / 255) as u8));
+}
+
+/// Draw the action safe and title safe rects and a center cross over `frame`
+/// as one pixel, semi-transparent white lines.
+pub(crate) fn draw(frame: &mut [u32], width: usize, height: usize) {
+    if width == 0 || height == 0 {
+        return;
+    }
+    for area in SAFE_AREAS {
+        let (w, h) = (width as f32 * area, height as f32 * area);
+        let left = ((width as f32 - w) / 2.0) as usize;
+        let top = ((height as f32 - h) / 2.0) as usize;
+        let (right, bottom) = (width - 1 - left, height - 1 - top);
+        for x in left..=right {
+            plot(frame, width, x, top);
+            if bottom != top {
+                plot(frame, width, x, bottom);
+            }
+        }
+        for y in top + 1..bottom {
+            plot(frame, width, left, y);
+            if right != left {
+                plot(frame, width, right, y);
+            }
+        }
+    }
+    let (cx, cy) = (width / 2, height / 2);
+    let arm = (width.min(height) as f32 * CROSS) as usize;
+    for x in cx.saturating_sub(arm)..=(cx + arm).min(width - 1) {
+        plot(frame, width, x, cy);
+    }
+    for y in cy.saturating_sub(arm)..=(cy + arm).min(height - 1) {
+        if y != cy {
+            plot(frame, width, cx, y);
+        }
+    }
+}
+
+/// Draw a one pixel opaque outline just inside the `[x, y, width, height]`
+/// rect, clipped to `frame`.
+pub(crate) fn outline(frame: &mut [u32], width: usize, height: usize, rect: [f32; 4]) {
+    let [x, y, w, h] = rect;
+    let (left, top) = (x.round(), y.round());
+    let (right, bottom) = ((x + w).round() - 1.0, (y + h).round() - 1.0);
+    if width == 0 || height == 0 || right < left || bottom < top {
+        return;
+    }
+    let inside = |value: f32, len: usize| (0.0..len as f32).contains(&value);
+    let clamp = |value: f32, len: usize| value.clamp(0.0, len as f32 - 1.0) as usize;
+    for column in clamp(left, width)..=clamp(right, width) {
+        for row in [top, bottom] {
+            if inside(row, height) {
+                frame[row as usize * width + column] = OUTLINE;
+            }
+        }
+    }
+    for row in clamp(top, height)..=clamp(bottom, height) {
+        for column in [left, right] {
+            if inside(column, width) {
+                frame[row * width + column as usize] = OUTLINE;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw() {
+        let (width, height) = (100, 50);
+        let mut frame = vec![0; width * height];
+        draw(&mut frame, width, height);
+        let alpha = |x: usize, y: usize| pixel::unpack(frame[y * width + x])[3];
+        // Action safe corner, title safe edge and the center
+        assert_eq!(alpha(5, 2), 128);
+        assert_eq!(alpha(10, 30), 128);
+        assert_eq!(alpha(50, 25), 128);
+        // Outside the guides is untouched
+        assert_eq!(alpha(0, 0), 0);
+        assert_eq!(alpha(30, 10), 0);
+    }
+
+    #[test]
+    fn test_outline() {
+        let mut frame = vec![0; 6 * 4];
+        outline(&mut frame, 6, 4, [1.0, 1.0, 3.0, 2.0]);
+        #[rustfmt::skip]
+        let expected = [
+            0, 0, 0, 0, 0, 0,
+            0, 1, 1, 1, 0, 0,
+            0, 1, 1, 1, 0, 0,
+            0, 0, 0, 0, 0, 0,
+        ];
+        assert_eq!(frame, expected.map(|p| p * OUTLINE));
+
+        // Clipped edges are not drawn
+        let mut frame = vec![0; 4 * 4];
+        outline(&mut frame, 4, 4, [-2.0, 1.0, 10.0, 2.0]);
+        assert_eq!(frame[4..8], [OUTLINE; 4]);
+        assert_eq!(frame[0..4], [0; 4]);
+    }
+
+    #[test]
+    fn test_draw_opaque() {
+        let mut frame = vec![pixel::pack([0, 0, 0, 255]); 10 * 10];
+        draw(&mut frame, 10, 10);
+        assert_eq!(pixel::unpack(frame[5 * 10 + 5]), [128, 128, 128, 255]);
+    }
+}
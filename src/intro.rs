@@ -0,0 +1,87 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/// Play through an intro once, then loop over a frame range.
+/// Negative values are unset.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct IntroLoop {
+    // Defaults to loop_start
+    pub intro_end: f32,
+    pub loop_start: f32,
+    pub loop_end: f32,
+}
+
+impl Default for IntroLoop {
+    fn default() -> Self {
+        IntroLoop {
+            intro_end: -1.0,
+            loop_start: -1.0,
+            loop_end: -1.0,
+        }
+    }
+}
+
+impl IntroLoop {
+    /// Map the linear `frame` into the intro or loop range,
+    /// None if no loop range is set.
+    pub(crate) fn frame(&self, frame: f64) -> Option<f32> {
+        if self.loop_start < 0.0 || self.loop_end <= self.loop_start {
+            return None;
+        }
+        let intro_end = if self.intro_end < 0.0 {
+            self.loop_start
+        } else {
+            self.intro_end
+        } as f64;
+        let frame = frame.max(0.0);
+        if frame < intro_end {
+            return Some(frame as f32);
+        }
+        let loop_length = (self.loop_end - self.loop_start) as f64;
+        Some(self.loop_start + ((frame - intro_end) % loop_length) as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unset() {
+        assert_eq!(IntroLoop::default().frame(50.0), None);
+        let empty = IntroLoop {
+            loop_start: 30.0,
+            loop_end: 30.0,
+            ..Default::default()
+        };
+        assert_eq!(empty.frame(50.0), None);
+    }
+
+    #[test]
+    fn test_intro_then_loop() {
+        let intro_loop = IntroLoop {
+            loop_start: 30.0,
+            loop_end: 90.0,
+            ..Default::default()
+        };
+        assert_eq!(intro_loop.frame(-5.0), Some(0.0));
+        assert_eq!(intro_loop.frame(10.0), Some(10.0));
+        assert_eq!(intro_loop.frame(30.0), Some(30.0));
+        assert_eq!(intro_loop.frame(89.0), Some(89.0));
+        assert_eq!(intro_loop.frame(90.0), Some(30.0));
+        assert_eq!(intro_loop.frame(100.0), Some(40.0));
+        assert_eq!(intro_loop.frame(1.0e9 + 30.0), Some(70.0));
+    }
+
+    #[test]
+    fn test_intro_end() {
+        let intro_loop = IntroLoop {
+            intro_end: 20.0,
+            loop_start: 60.0,
+            loop_end: 90.0,
+        };
+        assert_eq!(intro_loop.frame(19.0), Some(19.0));
+        assert_eq!(intro_loop.frame(20.0), Some(60.0));
+        assert_eq!(intro_loop.frame(55.0), Some(65.0));
+    }
+}
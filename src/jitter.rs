@@ -0,0 +1,33 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// SplitMix64 finalizer, spreads consecutive indices over all bits
+fn mix(index: i64) -> u64 {
+    let mut z = (index as u64).wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Deterministic offset in frames for frame `index`, uniformly distributed
+/// in `[-amount / 2, amount / 2)`.
+pub(crate) fn offset(index: i64, amount: f64) -> f64 {
+    let unit = (mix(index) >> 11) as f64 / (1u64 << 53) as f64;
+    (unit - 0.5) * amount
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset() {
+        for index in -100..100 {
+            let offset = offset(index, 0.5);
+            assert!((-0.25..0.25).contains(&offset), "{offset}");
+        }
+        assert_eq!(offset(7, 0.5), offset(7, 0.5));
+        assert_ne!(offset(7, 0.5), offset(8, 0.5));
+        assert_eq!(offset(7, 0.0), 0.0);
+    }
+}
@@ -1,12 +1,112 @@
 // Copyright (C) 2025 Andrew Wason
 // SPDX-License-Identifier: GPL-3.0-or-later
+mod blur;
+mod burnin;
+mod cache;
+mod chromatic;
+mod color;
+mod config;
+mod dither;
+mod dotlottie;
+mod dwell;
+mod edge;
+mod engine;
+mod fallback;
+mod field;
 mod fit;
+mod gradient;
+mod guides;
+mod intro;
+mod jitter;
+mod logging;
+mod mask;
+mod metadata;
+mod mirror;
 mod mode;
-use std::ffi::CString;
+mod pixel;
+#[cfg(feature = "remote")]
+mod remote;
+mod rounding;
+mod shutter;
+mod source;
+mod status;
+mod text;
+mod timemap;
+mod vignette;
+use std::{
+    cell::OnceCell,
+    ffi::{CStr, CString},
+    time::{Duration, Instant, SystemTime},
+};
 
 use anyhow::Context;
 use dotlottie_rs::{Animation, ColorSpace, Drawable, Renderer, Shape};
-use ureq::http::Uri;
+
+// Consecutive draw failures tolerated before they are treated as fatal
+const MAX_CONSECUTIVE_DRAW_ERRORS: u32 = 10;
+const MAX_LOAD_FAILURES: u32 = 3;
+// Loaded when animation and animation_json are empty
+#[cfg(feature = "embedded_animation")]
+const EMBEDDED_ANIMATION: &str = include_str!(env!("L0TTIE_EMBEDDED_ANIMATION_PATH"));
+// blur_fill blur radius as a fraction of the larger frame dimension
+const BLUR_FILL_DIVISOR: usize = 32;
+// Frame rate assumed for animations with frames but no duration
+const FALLBACK_FRAME_RATE: f32 = 30.0;
+// Opacity of the earlier frame drawn behind the current one by onion_skin
+const ONION_SKIN_OPACITY: f32 = 0.4;
+// Upper bound on motion_samples, each sample is a full render
+const MAX_MOTION_SAMPLES: usize = 64;
+
+fn engine_info() -> CString {
+    let dotlottie = option_env!("L0TTIE_DOTLOTTIE_VERSION").unwrap_or("unknown");
+    CString::new(format!(
+        "l0ttie {}, dotlottie-rs {dotlottie} (ThorVG v0)",
+        env!("CARGO_PKG_VERSION")
+    ))
+    .unwrap_or_default()
+}
+
+// Animation duration, synthesized from the frame count at FALLBACK_FRAME_RATE
+// when a malformed frame rate reports frames but no duration
+fn effective_duration(duration: f32, total_frames: f32) -> f32 {
+    if duration <= 0.0 && total_frames > 0.0 {
+        total_frames / FALLBACK_FRAME_RATE
+    } else {
+        duration
+    }
+}
+
+// Compare colors by component, setters skip work when a push repeats the current value
+fn same_color(a: &frei0r_rs2::Color, b: &frei0r_rs2::Color) -> bool {
+    (a.r, a.g, a.b) == (b.r, b.g, b.b)
+}
+
+// Parameters reported to the host, left out of config_string
+const READ_ONLY_PARAMS: &[&CStr] = &[
+    c"last_error",
+    c"anim_width",
+    c"anim_height",
+    c"anim_name",
+    c"draw_errors",
+    c"animation_count",
+    c"engine_info",
+    c"finished",
+    c"duration",
+    c"total_frames",
+    c"status",
+    c"config",
+];
+
+// Background and bars drawn by their own renderer while crop_clip is set,
+// so clipping the main scene only clips the animation
+struct Decor {
+    // Declared first to drop before the shapes it references
+    renderer: dotlottie_rs::TvgRenderer,
+    background_shape: dotlottie_rs::TvgShape,
+    bar_shape: dotlottie_rs::TvgShape,
+    frame: Vec<u32>,
+    redraw: bool,
+}
 
 pub struct L0ttiePlugin {
     animation_path: CString,
@@ -15,12 +115,165 @@ pub struct L0ttiePlugin {
     layout: dotlottie_rs::Layout,
     time_scale: f64,
     background_color: Option<frei0r_rs2::Color>,
+    last_error: CString,
+    cache_ttl: f64,
+    read_retries: f64,
+    read_retry_delay_ms: f64,
+    fit_content: bool,
+    background_radius: f64,
+    rebuild_background: bool,
+    crop_x: f64,
+    crop_y: f64,
+    crop_w: f64,
+    crop_h: f64,
+    // Output pixel rect the crop maps to, outside is cleared
+    crop_clip: Option<[f32; 4]>,
+    auto_background: bool,
+    // Sampled from the first rendered frame after loading
+    auto_background_color: Option<[u8; 4]>,
+    sample_background: bool,
+    exposure: f64,
+    exposure_lut: color::Lut,
+    status: status::Status,
+    border_width: f64,
+    border_color: frei0r_rs2::Color,
+    border_shape: Option<dotlottie_rs::TvgShape>,
+    bar_color: Option<frei0r_rs2::Color>,
+    bar_shape: Option<dotlottie_rs::TvgShape>,
+    frame_lock: bool,
+    json_logs: bool,
+    base_dir: CString,
+    goto_marker: f64,
+    // Start frame of the goto_marker marker to hold
+    goto_frame: Option<f32>,
+    background_gradient: gradient::Gradient,
+    gradient_start: frei0r_rs2::Color,
+    gradient_end: frei0r_rs2::Color,
+    // Rendered gradient, empty when it needs to be redrawn
+    gradient_frame: Vec<u32>,
+    snap_to_pixels: bool,
+    bounce_skip_endpoints: bool,
+    region_x: f64,
+    region_y: f64,
+    region_w: f64,
+    region_h: f64,
+    region_frame: Vec<u32>,
+    animation_json: CString,
+    step_frames: f64,
+    flip_output: bool,
+    visible_start: f64,
+    visible_end: f64,
+    safe_guides: bool,
+    // Per-axis overrides of layout.fit
+    fit_x: Option<dotlottie_rs::Fit>,
+    fit_y: Option<dotlottie_rs::Fit>,
+    max_frame: f64,
+    vignette_strength: f64,
+    vignette_radius: f64,
+    vignette: vignette::Vignette,
+    markers_span: bool,
+    temporal_jitter: f64,
+    theme_path: CString,
+    onion_skin: f64,
+    time_map_param: CString,
+    time_map: Option<timemap::TimeMap>,
+    debug_bounds: bool,
+    // Frame rect occupied by the whole laid out animation
+    animation_rect: Option<[f32; 4]>,
+    marker_dwell: f64,
+    realtime: bool,
+    // First render since load when realtime is set
+    realtime_start: Option<Instant>,
+    // config_string(), built when first read after each update_source
+    config: OnceCell<CString>,
+    // Play straight through the animation, see frames()
+    linear: bool,
+    // Drawn bounds at frame 0 in animation coordinates, for fit_content
+    content_bounds: Option<[f32; 4]>,
+    dither: bool,
+    pixel_aspect: f64,
+    native_size: bool,
+    channel_phase: f64,
+    lock_layout: bool,
+    anim_width: f64,
+    anim_height: f64,
+    anim_name: CString,
+    bounce_hold: f64,
+    draw_errors: u32,
+    consecutive_draw_errors: u32,
+    last_good_frame: Vec<u32>,
+    tint: frei0r_rs2::Color,
+    tint_strength: f64,
+    saturation: f64,
+    max_time_scale: f64,
+    mirror: mirror::Mirror,
+    fallback: fallback::Fallback,
+    frame_rounding: rounding::FrameRounding,
+    max_fps: f64,
+    last_render: Option<(Instant, Option<f32>)>,
+    align_y_anim: bool,
+    pan_speed: f64,
+    output_gamma: f64,
+    gamma_lut: color::Lut,
+    animation_index: f64,
+    animation_count: usize,
+    text_replacements: CString,
+    edge: edge::Edge,
+    engine_info: CString,
+    alpha_threshold: f64,
+    invert_alpha: bool,
+    intro_loop: intro::IntroLoop,
+    interlaced: bool,
+    field_order: field::FieldOrder,
+    motion_blur: f64,
+    motion_samples: f64,
+    blur_fill: bool,
+    reset: bool,
+    mask: mask::Mask,
+    mask_radius: f64,
+    delta_time: bool,
+    playhead: f64,
+    // Animation time (after time_scale) of the last render or set_time
+    current_time: f64,
+    load_failures: u32,
+    failed_source: Option<(CString, Option<SystemTime>)>,
+    // Keyed by frame number bits, size and layout_generation
+    frame_cache: cache::FrameCache<(u32, usize, usize, u64)>,
+    matte_color: Option<frei0r_rs2::Color>,
+    burn_in: bool,
+    background_rgba_param: CString,
+    background_rgba: Option<[u8; 4]>,
+    finished: bool,
+    renderer_config: CString,
+    marker: CString,
+    markers: Vec<metadata::Marker>,
+    // Start frame and frame count of the selected marker
+    marker_range: Option<(f32, f32)>,
+    anim_duration: f64,
+    anim_total_frames: f64,
+    fps_hint: f64,
+    color_matrix_param: CString,
+    color_matrix: Option<color::Matrix>,
     width: usize,
     height: usize,
     renderer: dotlottie_rs::TvgRenderer,
     animation: dotlottie_rs::TvgAnimation,
     background_shape: Option<dotlottie_rs::TvgShape>,
+    decor: Option<Decor>,
+    // Whether the animation is laid out by compute_layout(true) for blur_fill
+    cover_layout: bool,
+    scratch: Vec<Vec<u32>>,
+    rgba_frame: Vec<u32>,
+    field_frame: Vec<u32>,
+    onion_frame: Vec<u32>,
+    motion_accum: Vec<u32>,
+    blur_frame: Vec<u32>,
+    blur_line: Vec<[u8; 4]>,
     recompute_layout: bool,
+    layout_computed: bool,
+    // Incremented whenever the layout transform is applied
+    layout_generation: u64,
+    last_frame: Option<f32>,
     initialized: bool,
     loaded: bool,
 }
@@ -45,10 +298,15 @@ impl frei0r_rs2::Plugin for L0ttiePlugin {
         ),
         frei0r_rs2::ParamInfo::new_string(
             c"mode",
-            c"Playback mode: 'forward' (default), 'reverse', 'bounce', 'reverse-bounce'",
+            c"Playback mode: 'forward' (default), 'reverse', 'bounce', 'reverse-bounce', 'once'",
             |plugin| plugin.mode.into(),
             |plugin, value| {
-                plugin.mode = mode::Mode::from(value);
+                let mode = mode::Mode::from(value);
+                // finished only applies to the mode it was computed for
+                if plugin.mode != mode {
+                    plugin.mode = mode;
+                    plugin.finished = false;
+                }
             }
         ),
         frei0r_rs2::ParamInfo::new_bool(
@@ -64,182 +322,3229 @@ impl frei0r_rs2::Plugin for L0ttiePlugin {
             c"Fit animation to video frame: 'contain' (default), 'fill', 'cover', 'fit-width', 'fit-height', 'none'",
             |plugin| fit::Fit(plugin.layout.fit).into(),
             |plugin, value| {
-                plugin.layout.fit = fit::Fit::from(value).0;
-                plugin.recompute_layout = true;
+                let fit = fit::Fit::from(value).0;
+                if plugin.layout.fit != fit {
+                    plugin.layout.fit = fit;
+                    plugin.recompute_layout = true;
+                }
             }
         ),
         frei0r_rs2::ParamInfo::new_color(
             c"background_color",
             c"Background color",
-            |plugin| plugin.background_color.unwrap_or(frei0r_rs2::Color { r:0.0, g:0.0, b:0.0}),
+            |plugin| plugin.background_color.unwrap_or(config::UNSET_COLOR),
             |plugin, value| {
-                plugin.background_color = Some(*value);
+                plugin.background_color = config::optional_color(value);
             }
         ),
-    ];
-
-    fn info() -> frei0r_rs2::PluginInfo {
-        frei0r_rs2::PluginInfo {
-            name: c"l0ttie",
-            author: c"Andrew Wason",
-            color_model: frei0r_rs2::ColorModel::RGBA8888,
-            major_version: env!("CARGO_PKG_VERSION_MAJOR").parse().unwrap(),
-            minor_version: env!("CARGO_PKG_VERSION_MINOR").parse().unwrap(),
-            explanation: Some(c"Lottie renderer using dotlottie-rs"),
-        }
-    }
-
-    fn new(width: usize, height: usize) -> Self {
-        Self {
-            animation_path: c"".into(),
-            width,
-            height,
-            mode: mode::Mode::Forward,
-            loop_animation: false,
-            time_scale: 1.0,
-            layout: dotlottie_rs::Layout::new(dotlottie_rs::Fit::Contain, vec![0.5, 0.5]),
-            background_color: None,
-            renderer: dotlottie_rs::TvgRenderer::new(dotlottie_rs::TvgEngine::TvgEngineSw, 0),
-            animation: dotlottie_rs::TvgAnimation::default(),
-            background_shape: None,
-            recompute_layout: true,
-            initialized: false,
-            loaded: false,
-        }
-    }
-}
-
-impl frei0r_rs2::SourcePlugin for L0ttiePlugin {
-    fn update_source(&mut self, time: f64, outframe: &mut [u32]) {
-        if let Err(err) = self.renderer.set_target(
-            outframe,
-            self.width as u32,
-            self.width as u32,
-            self.height as u32,
-            ColorSpace::ABGR8888,
-        ) {
-            eprintln!("Failed to set render target: {err:?}");
-            return;
-        }
-        if !self.initialized {
-            if let Err(err) = self.initialize() {
-                eprintln!("Failed to initialize plugin: {err:?}");
-                return;
+        frei0r_rs2::ParamInfo::new_string(
+            c"last_error",
+            c"Last load or render error (read-only)",
+            |plugin| plugin.last_error.as_c_str(),
+            |_plugin, _value| {}
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"cache_ttl",
+            c"Seconds before a cached animation URL is revalidated",
+            |plugin| plugin.cache_ttl,
+            |plugin, value| {
+                plugin.cache_ttl = value;
             }
-        }
-        if !self.loaded {
-            return;
-        }
-
-        if let Err(err) = self.render(time * self.time_scale) {
-            eprintln!("Failed to render: {err:?}");
-        }
-    }
-}
-
-impl L0ttiePlugin {
-    fn initialize(&mut self) -> anyhow::Result<()> {
-        self.initialized = true;
-        let animation_path = self
-            .animation_path
-            .to_str()
-            .with_context(|| format!("Invalid lottie animation path: {:?}", self.animation_path))?;
-
-        let data = if let Ok(animation_uri) = animation_path.parse::<Uri>() {
-            if animation_uri.scheme().is_some() {
-                ureq::get(animation_path)
-                    .call()
-                    .with_context(|| {
-                        format!("Failed to load lottie animation url: {animation_path}")
-                    })?
-                    .body_mut()
-                    .read_to_string()
-                    .with_context(|| {
-                        format!("Failed to read lottie animation url: {animation_path}")
-                    })?
-            } else {
-                std::fs::read_to_string(animation_path).with_context(|| {
-                    format!("Failed to read lottie animation path: {animation_path}")
-                })?
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"dither",
+            c"Apply ordered dithering to reduce gradient banding",
+            |plugin| plugin.dither,
+            |plugin, value| {
+                plugin.dither = value;
             }
-        } else {
-            std::fs::read_to_string(animation_path).with_context(|| {
-                format!("Failed to read lottie animation path: {animation_path}")
-            })?
-        };
-
-        self.animation
-            .load_data(&data, "lottie", true)
-            .with_context(|| format!("Failed to load lottie animation path: {animation_path}"))?;
-        if let Some(background_color) = self.background_color {
-            let mut background_shape = dotlottie_rs::TvgShape::default();
-            background_shape
-                .append_rect(0.0, 0.0, self.width as f32, self.height as f32, 0.0, 0.0)
-                .context("Failed to construct background shape")?;
-            background_shape
-                .fill((
-                    (background_color.r * 255.0) as u8,
-                    (background_color.g * 255.0) as u8,
-                    (background_color.b * 255.0) as u8,
-                    255,
-                ))
-                .context("Failed to fill background shape")?;
-            self.renderer
-                .push(Drawable::Shape(&background_shape))
-                .context("Failed to add background shape")?;
-            self.background_shape = Some(background_shape);
-        }
-        self.renderer
-            .push(Drawable::Animation(&self.animation))
-            .context("Failed to add animation")?;
-        self.loaded = true;
-        Ok(())
-    }
-
-    fn compute_layout(&mut self) -> anyhow::Result<()> {
-        let (animation_width, animation_height) = self.animation.get_size()?;
-        let (sx, sy, tx, ty) = self.layout.compute_layout_transform(
-            self.width as f32,
-            self.height as f32,
-            animation_width,
-            animation_height,
-        );
-        self.animation.set_size(sx, sy)?;
-        self.animation.translate(tx, ty)?;
-        Ok(())
-    }
-
-    fn render(&mut self, time: f64) -> anyhow::Result<()> {
-        if self.recompute_layout {
-            self.compute_layout().context("Failed to compute layout")?;
-            self.recompute_layout = false;
-        }
-
-        let duration = self
-            .animation
-            .get_duration()
-            .context("Failed to query duration")?;
-        let animation_time = self.mode.next_frame(time, duration, self.loop_animation);
-
-        // Convert animation time to frame number
-        let total_frames = self
-            .animation
-            .get_total_frame()
-            .context("Failed to query total frames")?;
-        let frame_number = if duration > 0.0 {
-            (animation_time / duration) * total_frames
-        } else {
-            0.0
-        };
-
-        // Ignore errors, fails if we set the same frame
-        let _ = self.animation.set_frame(frame_number);
-        self.renderer.update()?;
-        self.renderer.draw(true)?;
-        self.renderer.sync()?;
-
-        Ok(())
-    }
-}
-
-frei0r_rs2::plugin!(L0ttiePlugin);
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"pixel_aspect",
+            c"Pixel aspect ratio (display width / height) of the video frame",
+            |plugin| plugin.pixel_aspect,
+            |plugin, value| {
+                if plugin.pixel_aspect != value {
+                    plugin.pixel_aspect = value;
+                    plugin.recompute_layout = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"native_size",
+            c"Render the animation unscaled at its authored size, centered",
+            |plugin| plugin.native_size,
+            |plugin, value| {
+                if plugin.native_size != value {
+                    plugin.native_size = value;
+                    plugin.recompute_layout = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"channel_phase",
+            c"Seconds to offset the green and blue channel playheads from red",
+            |plugin| plugin.channel_phase,
+            |plugin, value| {
+                plugin.channel_phase = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"lock_layout",
+            c"Ignore layout changes once the layout has been computed",
+            |plugin| plugin.lock_layout,
+            |plugin, value| {
+                plugin.lock_layout = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"anim_width",
+            c"Animation width (read-only)",
+            |plugin| plugin.anim_width,
+            |_plugin, _value| {}
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"anim_height",
+            c"Animation height (read-only)",
+            |plugin| plugin.anim_height,
+            |_plugin, _value| {}
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"anim_name",
+            c"Animation name (read-only)",
+            |plugin| plugin.anim_name.as_c_str(),
+            |_plugin, _value| {}
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"bounce_hold",
+            c"Seconds to hold at each end of a bounce",
+            |plugin| plugin.bounce_hold,
+            |plugin, value| {
+                plugin.bounce_hold = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"draw_errors",
+            c"Number of frames that failed to draw (read-only)",
+            |plugin| plugin.draw_errors as f64,
+            |_plugin, _value| {}
+        ),
+        frei0r_rs2::ParamInfo::new_color(
+            c"tint",
+            c"Color to multiply the animation by",
+            |plugin| plugin.tint,
+            |plugin, value| {
+                plugin.tint = *value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"tint_strength",
+            c"Strength of the tint (0-1)",
+            |plugin| plugin.tint_strength,
+            |plugin, value| {
+                plugin.tint_strength = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"saturation",
+            c"Color saturation, 0 is grayscale",
+            |plugin| plugin.saturation,
+            |plugin, value| {
+                plugin.saturation = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"max_time_scale",
+            c"Maximum magnitude of time_scale, 0 for no limit",
+            |plugin| plugin.max_time_scale,
+            |plugin, value| {
+                plugin.max_time_scale = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"mirror",
+            c"Mirror the frame: 'none' (default), 'horizontal', 'vertical', 'quad'",
+            |plugin| plugin.mirror.into(),
+            |plugin, value| {
+                plugin.mirror = mirror::Mirror::from(value);
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"fallback",
+            c"Render when the animation fails to load: 'transparent' (default), 'background', 'error-pattern'",
+            |plugin| plugin.fallback.into(),
+            |plugin, value| {
+                plugin.fallback = fallback::Fallback::from(value);
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"frame_rounding",
+            c"Fractional frame rounding: 'none' (default), 'floor', 'round', 'ceil'",
+            |plugin| plugin.frame_rounding.into(),
+            |plugin, value| {
+                plugin.frame_rounding = rounding::FrameRounding::from(value);
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"max_fps",
+            c"Maximum wall clock frames per second to render, 0 for no limit",
+            |plugin| plugin.max_fps,
+            |plugin, value| {
+                plugin.max_fps = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"align_y_anim",
+            c"Pan vertically across animation overflow",
+            |plugin| plugin.align_y_anim,
+            |plugin, value| {
+                if plugin.align_y_anim != value {
+                    plugin.align_y_anim = value;
+                    if !value {
+                        plugin.layout.align[1] = 0.5;
+                        plugin.recompute_layout = true;
+                    }
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"pan_speed",
+            c"Vertical pan speed in overflow heights per second",
+            |plugin| plugin.pan_speed,
+            |plugin, value| {
+                plugin.pan_speed = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"output_gamma",
+            c"Gamma exponent applied to output colors, 1.0 is unchanged",
+            |plugin| plugin.output_gamma,
+            |plugin, value| {
+                if value != plugin.output_gamma {
+                    plugin.output_gamma = value;
+                    plugin.gamma_lut = color::gamma_lut(value as f32);
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"animation_index",
+            c"Index of the dotLottie animation to play, -1 for the active animation",
+            |plugin| plugin.animation_index,
+            |plugin, value| {
+                plugin.animation_index = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"animation_count",
+            c"Number of animations in the dotLottie file (read-only)",
+            |plugin| plugin.animation_count as f64,
+            |_plugin, _value| {}
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"text_replacements",
+            c"Replace named text layers, 'name=value;name2=value2'",
+            |plugin| plugin.text_replacements.as_c_str(),
+            |plugin, value| plugin.text_replacements = value.to_owned(),
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"edge",
+            c"Outside the playback cycle: 'none' (default, per mode and loop), 'clamp', 'loop', 'hold-black'",
+            |plugin| plugin.edge.into(),
+            |plugin, value| {
+                plugin.edge = edge::Edge::from(value);
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"engine_info",
+            c"l0ttie, dotlottie-rs and ThorVG versions (read-only)",
+            |plugin| plugin.engine_info.as_c_str(),
+            |_plugin, _value| {}
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"alpha_threshold",
+            c"Alpha threshold (0..1) for a hard matte, 0 disables",
+            |plugin| plugin.alpha_threshold,
+            |plugin, value| {
+                plugin.alpha_threshold = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"intro_end",
+            c"Frame where the intro ends and looping starts, -1 for loop_start",
+            |plugin| plugin.intro_loop.intro_end as f64,
+            |plugin, value| {
+                plugin.intro_loop.intro_end = value as f32;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"loop_start",
+            c"First frame of the loop range after the intro, -1 disables",
+            |plugin| plugin.intro_loop.loop_start as f64,
+            |plugin, value| {
+                plugin.intro_loop.loop_start = value as f32;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"loop_end",
+            c"End frame of the loop range after the intro, -1 disables",
+            |plugin| plugin.intro_loop.loop_end as f64,
+            |plugin, value| {
+                plugin.intro_loop.loop_end = value as f32;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"interlaced",
+            c"Render two fields half an animation frame apart on alternating rows",
+            |plugin| plugin.interlaced,
+            |plugin, value| {
+                plugin.interlaced = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"field_order",
+            c"Interlaced field order: 'tff' (default, top field first), 'bff'",
+            |plugin| plugin.field_order.into(),
+            |plugin, value| {
+                plugin.field_order = field::FieldOrder::from(value);
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"motion_blur",
+            c"Motion blur shutter as a fraction (0..1) of an animation frame, 0 disables",
+            |plugin| plugin.motion_blur,
+            |plugin, value| {
+                plugin.motion_blur = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"motion_samples",
+            c"Number of samples averaged for motion blur, at most 64",
+            |plugin| plugin.motion_samples,
+            |plugin, value| {
+                plugin.motion_samples = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"blur_fill",
+            c"Fill the margins around the fitted animation with a blurred cover scaled copy",
+            |plugin| plugin.blur_fill,
+            |plugin, value| {
+                plugin.blur_fill = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"reset",
+            c"Reload the animation on the next render, clears itself",
+            |plugin| plugin.reset,
+            |plugin, value| {
+                plugin.reset = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"mask",
+            c"Clip output to a shape: 'none' (default), 'circle', 'rounded'",
+            |plugin| plugin.mask.into(),
+            |plugin, value| {
+                plugin.mask = mask::Mask::from(value);
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"mask_radius",
+            c"Circle radius or rounded corner radius in pixels, 0 for the largest that fits",
+            |plugin| plugin.mask_radius,
+            |plugin, value| {
+                plugin.mask_radius = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"delta_time",
+            c"Treat time as the increment since the previous frame and accumulate it",
+            |plugin| plugin.delta_time,
+            |plugin, value| {
+                plugin.delta_time = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"frame_cache_size",
+            c"Number of rendered frames to cache for scrubbing, 0 disables",
+            |plugin| plugin.frame_cache.capacity() as f64,
+            |plugin, value| {
+                plugin.frame_cache.set_capacity(value.max(0.0) as usize);
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_color(
+            c"matte_color",
+            c"Flatten output onto this color, making it opaque",
+            |plugin| plugin.matte_color.unwrap_or(config::UNSET_COLOR),
+            |plugin, value| {
+                plugin.matte_color = config::optional_color(value);
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"burn_in",
+            c"Draw the frame number and animation time in the top left corner",
+            |plugin| plugin.burn_in,
+            |plugin, value| {
+                plugin.burn_in = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"background_rgba",
+            c"Background color with alpha as '#RRGGBBAA', overrides background_color",
+            |plugin| plugin.background_rgba_param.as_c_str(),
+            |plugin, value| {
+                if plugin.background_rgba_param.as_c_str() == value {
+                    return;
+                }
+                plugin.background_rgba_param = value.to_owned();
+                let value = value.to_string_lossy();
+                let color = color::parse_hex_rgba(&value);
+                if color.is_none() && !value.trim().is_empty() {
+                    plugin.report_error(format!("Invalid background_rgba color: {value}"));
+                }
+                if plugin.background_rgba != color {
+                    plugin.background_rgba = color;
+                    plugin.rebuild_background = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"finished",
+            c"Whether 'once' playback has reached its final frame (read-only)",
+            |plugin| plugin.finished,
+            |_plugin, _value| {}
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"renderer_config",
+            c"Renderer options applied when the animation loads, 'engine=sw;threads=4'",
+            |plugin| plugin.renderer_config.as_c_str(),
+            |plugin, value| plugin.renderer_config = value.to_owned(),
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"marker",
+            c"Name of the marker whose frame range is played, empty for the full animation",
+            |plugin| plugin.marker.as_c_str(),
+            |plugin, value| {
+                if plugin.marker.as_c_str() == value {
+                    return;
+                }
+                plugin.marker = value.to_owned();
+                if plugin.loaded {
+                    plugin.resolve_marker();
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"duration",
+            c"Duration in seconds of the animation or selected marker (read-only)",
+            |plugin| match plugin.marker_range {
+                Some((_, frames)) if plugin.anim_total_frames > 0.0 => {
+                    frames as f64 * plugin.anim_duration / plugin.anim_total_frames
+                }
+                _ => plugin.anim_duration,
+            },
+            |_plugin, _value| {}
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"total_frames",
+            c"Number of frames in the animation or selected marker (read-only)",
+            |plugin| plugin
+                .marker_range
+                .map_or(plugin.anim_total_frames, |(_, frames)| frames as f64),
+            |_plugin, _value| {}
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"fps_hint",
+            c"Host frame rate, makes time_scale the animation frames advanced per host frame, 0 disables",
+            |plugin| plugin.fps_hint,
+            |plugin, value| {
+                plugin.fps_hint = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"color_matrix",
+            c"Color matrix of 9 (3x3) or 12 (3x4 with offsets) comma separated values",
+            |plugin| plugin.color_matrix_param.as_c_str(),
+            |plugin, value| {
+                if plugin.color_matrix_param.as_c_str() == value {
+                    return;
+                }
+                plugin.color_matrix_param = value.to_owned();
+                let value = value.to_string_lossy();
+                plugin.color_matrix = None;
+                if !value.trim().is_empty() {
+                    match color::parse_matrix(&value) {
+                        Ok(matrix) => plugin.color_matrix = Some(matrix),
+                        Err(err) => plugin.report_error(format!("{err:?}")),
+                    }
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"invert_alpha",
+            c"Invert the alpha channel",
+            |plugin| plugin.invert_alpha,
+            |plugin, value| {
+                plugin.invert_alpha = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"read_retries",
+            c"Times a failed animation read or fetch is retried",
+            |plugin| plugin.read_retries,
+            |plugin, value| {
+                plugin.read_retries = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"read_retry_delay_ms",
+            c"Milliseconds to wait before retrying a failed animation read",
+            |plugin| plugin.read_retry_delay_ms,
+            |plugin, value| {
+                plugin.read_retry_delay_ms = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"fit_content",
+            c"Fit the drawn content bounds at frame 0 instead of the declared size",
+            |plugin| plugin.fit_content,
+            |plugin, value| {
+                if plugin.fit_content != value {
+                    plugin.fit_content = value;
+                    plugin.recompute_layout = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"background_radius",
+            c"Corner radius in pixels of the background",
+            |plugin| plugin.background_radius,
+            |plugin, value| {
+                if plugin.background_radius != value {
+                    plugin.background_radius = value;
+                    plugin.rebuild_background = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"crop_x",
+            c"Left of the crop rectangle in animation coordinates",
+            |plugin| plugin.crop_x,
+            |plugin, value| {
+                if plugin.crop_x != value {
+                    plugin.crop_x = value;
+                    plugin.recompute_layout = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"crop_y",
+            c"Top of the crop rectangle in animation coordinates",
+            |plugin| plugin.crop_y,
+            |plugin, value| {
+                if plugin.crop_y != value {
+                    plugin.crop_y = value;
+                    plugin.recompute_layout = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"crop_w",
+            c"Width of the crop rectangle in animation coordinates, 0 disables cropping",
+            |plugin| plugin.crop_w,
+            |plugin, value| {
+                if plugin.crop_w != value {
+                    plugin.crop_w = value;
+                    plugin.recompute_layout = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"crop_h",
+            c"Height of the crop rectangle in animation coordinates, 0 disables cropping",
+            |plugin| plugin.crop_h,
+            |plugin, value| {
+                if plugin.crop_h != value {
+                    plugin.crop_h = value;
+                    plugin.recompute_layout = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"auto_background",
+            c"Fill the background with the color of the top left pixel of the first frame",
+            |plugin| plugin.auto_background,
+            |plugin, value| {
+                plugin.auto_background = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"exposure",
+            c"Exposure adjustment in stops, 0 is unchanged",
+            |plugin| plugin.exposure,
+            |plugin, value| {
+                if value != plugin.exposure {
+                    plugin.exposure = value;
+                    plugin.exposure_lut = color::exposure_lut(value as f32);
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"status",
+            c"0 uninitialized, 1 loading, 2 rendering, 3 load failed, 4 render failed (read-only)",
+            |plugin| plugin.status as u8 as f64,
+            |_plugin, _value| {}
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"border_width",
+            c"Width in pixels of a border drawn around the fitted animation, 0 disables it",
+            |plugin| plugin.border_width,
+            |plugin, value| {
+                if plugin.border_width != value {
+                    plugin.border_width = value;
+                    plugin.recompute_layout = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_color(
+            c"border_color",
+            c"Border color",
+            |plugin| plugin.border_color,
+            |plugin, value| {
+                if !same_color(&plugin.border_color, value) {
+                    plugin.border_color = *value;
+                    plugin.recompute_layout = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"frame_lock",
+            c"Advance exactly one animation frame per render, ignoring time",
+            |plugin| plugin.frame_lock,
+            |plugin, value| {
+                if plugin.frame_lock != value {
+                    plugin.frame_lock = value;
+                    plugin.playhead = 0.0;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"json_logs",
+            c"Log errors to stderr as one line JSON records",
+            |plugin| plugin.json_logs,
+            |plugin, value| {
+                plugin.json_logs = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"base_dir",
+            c"Directory relative animation paths are resolved against",
+            |plugin| plugin.base_dir.as_c_str(),
+            |plugin, value| plugin.base_dir = value.to_owned(),
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"goto_marker",
+            c"Index of a marker whose start frame is held, -1 disables",
+            |plugin| plugin.goto_marker,
+            |plugin, value| {
+                if plugin.goto_marker != value {
+                    plugin.goto_marker = value;
+                    plugin.resolve_goto_marker();
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"background_gradient",
+            c"Background gradient replacing background_color: 'none' (default), 'vertical', 'horizontal', 'radial'",
+            |plugin| plugin.background_gradient.into(),
+            |plugin, value| {
+                let gradient = gradient::Gradient::from(value);
+                if plugin.background_gradient != gradient {
+                    plugin.background_gradient = gradient;
+                    plugin.gradient_frame.clear();
+                    plugin.rebuild_background = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_color(
+            c"gradient_start",
+            c"Background gradient start color",
+            |plugin| plugin.gradient_start,
+            |plugin, value| {
+                if !same_color(&plugin.gradient_start, value) {
+                    plugin.gradient_start = *value;
+                    plugin.gradient_frame.clear();
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_color(
+            c"gradient_end",
+            c"Background gradient end color",
+            |plugin| plugin.gradient_end,
+            |plugin, value| {
+                if !same_color(&plugin.gradient_end, value) {
+                    plugin.gradient_end = *value;
+                    plugin.gradient_frame.clear();
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"snap_to_pixels",
+            c"Round the layout position and size to whole pixels",
+            |plugin| plugin.snap_to_pixels,
+            |plugin, value| {
+                if plugin.snap_to_pixels != value {
+                    plugin.snap_to_pixels = value;
+                    plugin.recompute_layout = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"bounce_skip_endpoints",
+            c"Don't repeat the end frames when bounce turns around",
+            |plugin| plugin.bounce_skip_endpoints,
+            |plugin, value| {
+                plugin.bounce_skip_endpoints = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"region_x",
+            c"Left of the output region in pixels",
+            |plugin| plugin.region_x,
+            |plugin, value| {
+                if plugin.region_x != value {
+                    plugin.region_x = value;
+                    plugin.recompute_layout = true;
+                    plugin.rebuild_background = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"region_y",
+            c"Top of the output region in pixels",
+            |plugin| plugin.region_y,
+            |plugin, value| {
+                if plugin.region_y != value {
+                    plugin.region_y = value;
+                    plugin.recompute_layout = true;
+                    plugin.rebuild_background = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"region_w",
+            c"Width of the output region in pixels, 0 renders the full frame",
+            |plugin| plugin.region_w,
+            |plugin, value| {
+                if plugin.region_w != value {
+                    plugin.region_w = value;
+                    plugin.recompute_layout = true;
+                    plugin.rebuild_background = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"region_h",
+            c"Height of the output region in pixels, 0 renders the full frame",
+            |plugin| plugin.region_h,
+            |plugin, value| {
+                if plugin.region_h != value {
+                    plugin.region_h = value;
+                    plugin.recompute_layout = true;
+                    plugin.rebuild_background = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"animation_json",
+            c"Lottie animation JSON, used instead of the animation path when set",
+            |plugin| plugin.animation_json.as_c_str(),
+            |plugin, value| plugin.animation_json = value.to_owned(),
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"step_frames",
+            c"Only show every Nth animation frame for a stop motion look, 1 shows all",
+            |plugin| plugin.step_frames,
+            |plugin, value| {
+                plugin.step_frames = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"flip_output",
+            c"Reverse the output row order for hosts that expect the bottom row first",
+            |plugin| plugin.flip_output,
+            |plugin, value| {
+                plugin.flip_output = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"visible_start",
+            c"Host time in seconds before which the output is transparent",
+            |plugin| plugin.visible_start,
+            |plugin, value| {
+                plugin.visible_start = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"visible_end",
+            c"Host time in seconds after which the output is transparent, 0 for no end",
+            |plugin| plugin.visible_end,
+            |plugin, value| {
+                plugin.visible_end = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"safe_guides",
+            c"Draw action safe and title safe guides and a center cross for layout",
+            |plugin| plugin.safe_guides,
+            |plugin, value| {
+                plugin.safe_guides = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"fit_x",
+            c"Horizontal fit overriding 'fit', using the horizontal scale of that fit value, empty to use 'fit'",
+            |plugin| plugin.fit_x.map_or(c"", |fit| fit::Fit(fit).into()),
+            |plugin, value| {
+                let fit = (!value.is_empty()).then(|| fit::Fit::from(value).0);
+                if plugin.fit_x != fit {
+                    plugin.fit_x = fit;
+                    plugin.recompute_layout = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"fit_y",
+            c"Vertical fit overriding 'fit', using the vertical scale of that fit value, empty to use 'fit'",
+            |plugin| plugin.fit_y.map_or(c"", |fit| fit::Fit(fit).into()),
+            |plugin, value| {
+                let fit = (!value.is_empty()).then(|| fit::Fit::from(value).0);
+                if plugin.fit_y != fit {
+                    plugin.fit_y = fit;
+                    plugin.recompute_layout = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_color(
+            c"bar_color",
+            c"Color of the letterbox and pillarbox bars around the fitted animation",
+            |plugin| plugin.bar_color.unwrap_or(config::UNSET_COLOR),
+            |plugin, value| {
+                let value = config::optional_color(value);
+                let unchanged = match (&plugin.bar_color, &value) {
+                    (Some(color), Some(value)) => same_color(color, value),
+                    (current, value) => current.is_none() && value.is_none(),
+                };
+                if !unchanged {
+                    plugin.bar_color = value;
+                    plugin.recompute_layout = true;
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"max_frame",
+            c"Highest frame number played, later frames show it instead, -1 for no limit",
+            |plugin| plugin.max_frame,
+            |plugin, value| {
+                plugin.max_frame = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"vignette",
+            c"Strength (0-1) of a radial vignette darkening the frame edges, 0 disables",
+            |plugin| plugin.vignette_strength,
+            |plugin, value| {
+                plugin.vignette_strength = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"vignette_radius",
+            c"Fraction (0-1) of the center to corner distance where the vignette begins",
+            |plugin| plugin.vignette_radius,
+            |plugin, value| {
+                plugin.vignette_radius = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"markers_span",
+            c"Play from the first marker start to the last marker end when no marker is selected",
+            |plugin| plugin.markers_span,
+            |plugin, value| {
+                if plugin.markers_span != value {
+                    plugin.markers_span = value;
+                    if plugin.loaded {
+                        plugin.resolve_marker();
+                    }
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"temporal_jitter",
+            c"Fraction of a frame to deterministically jitter each sampled time by, 0 disables",
+            |plugin| plugin.temporal_jitter,
+            |plugin, value| {
+                plugin.temporal_jitter = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"theme_path",
+            c"Path of a JSON file of slot overrides applied when the animation loads",
+            |plugin| plugin.theme_path.as_c_str(),
+            |plugin, value| plugin.theme_path = value.to_owned(),
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"onion_skin",
+            c"Draw the frame this many animation frames earlier faded behind the current one, 0 disables",
+            |plugin| plugin.onion_skin,
+            |plugin, value| {
+                plugin.onion_skin = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"time_map",
+            c"Retime with 'host:animation' second pairs, 't0:a0,t1:a1', interpolated linearly",
+            |plugin| plugin.time_map_param.as_c_str(),
+            |plugin, value| {
+                if plugin.time_map_param.as_c_str() == value {
+                    return;
+                }
+                plugin.time_map_param = value.to_owned();
+                let value = value.to_string_lossy();
+                plugin.time_map = None;
+                if !value.trim().is_empty() {
+                    match timemap::TimeMap::parse(&value) {
+                        Ok(time_map) => plugin.time_map = Some(time_map),
+                        Err(err) => plugin.report_error(format!("{err:?}")),
+                    }
+                }
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"debug_bounds",
+            c"Outline the frame area the laid out animation occupies",
+            |plugin| plugin.debug_bounds,
+            |plugin, value| {
+                plugin.debug_bounds = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_double(
+            c"marker_dwell",
+            c"Seconds to pause at each marker start in forward and once modes, 0 disables",
+            |plugin| plugin.marker_dwell,
+            |plugin, value| {
+                plugin.marker_dwell = value;
+            }
+        ),
+        frei0r_rs2::ParamInfo::new_string(
+            c"config",
+            c"JSON of the writable parameters as of the last frame (read-only)",
+            |plugin| {
+                plugin
+                    .config
+                    .get_or_init(|| CString::new(plugin.config_string()).unwrap_or_default())
+                    .as_c_str()
+            },
+            |_plugin, _value| {}
+        ),
+        frei0r_rs2::ParamInfo::new_bool(
+            c"realtime",
+            c"Play by wall-clock seconds since the first frame, ignoring host time",
+            |plugin| plugin.realtime,
+            |plugin, value| {
+                plugin.realtime = value;
+            }
+        ),
+    ];
+
+    fn info() -> frei0r_rs2::PluginInfo {
+        frei0r_rs2::PluginInfo {
+            name: c"l0ttie",
+            author: c"Andrew Wason",
+            color_model: frei0r_rs2::ColorModel::RGBA8888,
+            major_version: env!("CARGO_PKG_VERSION_MAJOR").parse().unwrap(),
+            minor_version: env!("CARGO_PKG_VERSION_MINOR").parse().unwrap(),
+            explanation: Some(c"Lottie renderer using dotlottie-rs"),
+        }
+    }
+
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            animation_path: c"".into(),
+            width,
+            height,
+            mode: mode::Mode::Forward,
+            loop_animation: false,
+            time_scale: 1.0,
+            layout: dotlottie_rs::Layout::new(dotlottie_rs::Fit::Contain, vec![0.5, 0.5]),
+            background_color: None,
+            last_error: c"".into(),
+            cache_ttl: 0.0,
+            read_retries: 0.0,
+            read_retry_delay_ms: 100.0,
+            fit_content: false,
+            background_radius: 0.0,
+            rebuild_background: false,
+            crop_x: 0.0,
+            crop_y: 0.0,
+            crop_w: 0.0,
+            crop_h: 0.0,
+            crop_clip: None,
+            auto_background: false,
+            auto_background_color: None,
+            sample_background: false,
+            exposure: 0.0,
+            exposure_lut: color::exposure_lut(0.0),
+            status: status::Status::Uninitialized,
+            border_width: 0.0,
+            border_color: frei0r_rs2::Color {
+                r: 1.0,
+                g: 1.0,
+                b: 1.0,
+            },
+            border_shape: None,
+            bar_color: None,
+            bar_shape: None,
+            frame_lock: false,
+            json_logs: false,
+            base_dir: CString::default(),
+            goto_marker: -1.0,
+            goto_frame: None,
+            background_gradient: gradient::Gradient::None,
+            gradient_start: frei0r_rs2::Color {
+                r: 0.0,
+                g: 0.0,
+                b: 0.0,
+            },
+            gradient_end: frei0r_rs2::Color {
+                r: 1.0,
+                g: 1.0,
+                b: 1.0,
+            },
+            gradient_frame: Vec::new(),
+            snap_to_pixels: false,
+            bounce_skip_endpoints: false,
+            region_x: 0.0,
+            region_y: 0.0,
+            region_w: 0.0,
+            region_h: 0.0,
+            region_frame: Vec::new(),
+            animation_json: CString::default(),
+            step_frames: 1.0,
+            flip_output: false,
+            visible_start: 0.0,
+            visible_end: 0.0,
+            safe_guides: false,
+            fit_x: None,
+            fit_y: None,
+            max_frame: -1.0,
+            vignette_strength: 0.0,
+            vignette_radius: 0.5,
+            vignette: vignette::Vignette::default(),
+            markers_span: false,
+            temporal_jitter: 0.0,
+            theme_path: CString::default(),
+            onion_skin: 0.0,
+            time_map_param: CString::default(),
+            time_map: None,
+            debug_bounds: false,
+            animation_rect: None,
+            marker_dwell: 0.0,
+            realtime: false,
+            realtime_start: None,
+            config: OnceCell::new(),
+            linear: false,
+            content_bounds: None,
+            dither: false,
+            pixel_aspect: 1.0,
+            native_size: false,
+            channel_phase: 0.0,
+            lock_layout: false,
+            anim_width: 0.0,
+            anim_height: 0.0,
+            anim_name: c"".into(),
+            bounce_hold: 0.0,
+            draw_errors: 0,
+            consecutive_draw_errors: 0,
+            last_good_frame: Vec::new(),
+            tint: frei0r_rs2::Color {
+                r: 1.0,
+                g: 1.0,
+                b: 1.0,
+            },
+            tint_strength: 0.0,
+            saturation: 1.0,
+            max_time_scale: 1000.0,
+            mirror: mirror::Mirror::None,
+            fallback: fallback::Fallback::Transparent,
+            frame_rounding: rounding::FrameRounding::None,
+            max_fps: 0.0,
+            last_render: None,
+            align_y_anim: false,
+            pan_speed: 0.1,
+            output_gamma: 1.0,
+            gamma_lut: color::gamma_lut(1.0),
+            animation_index: -1.0,
+            animation_count: 0,
+            text_replacements: CString::default(),
+            edge: edge::Edge::None,
+            engine_info: engine_info(),
+            alpha_threshold: 0.0,
+            invert_alpha: false,
+            intro_loop: intro::IntroLoop::default(),
+            interlaced: false,
+            field_order: field::FieldOrder::Tff,
+            motion_blur: 0.0,
+            motion_samples: 4.0,
+            blur_fill: false,
+            reset: false,
+            mask: mask::Mask::None,
+            mask_radius: 0.0,
+            delta_time: false,
+            playhead: 0.0,
+            current_time: 0.0,
+            load_failures: 0,
+            failed_source: None,
+            frame_cache: cache::FrameCache::new(0),
+            matte_color: None,
+            burn_in: false,
+            background_rgba_param: CString::default(),
+            background_rgba: None,
+            finished: false,
+            renderer_config: CString::default(),
+            marker: CString::default(),
+            markers: Vec::new(),
+            marker_range: None,
+            anim_duration: 0.0,
+            anim_total_frames: 0.0,
+            fps_hint: 0.0,
+            color_matrix_param: CString::default(),
+            color_matrix: None,
+            renderer: dotlottie_rs::TvgRenderer::new(dotlottie_rs::TvgEngine::TvgEngineSw, 0),
+            animation: dotlottie_rs::TvgAnimation::default(),
+            background_shape: None,
+            decor: None,
+            cover_layout: false,
+            scratch: Vec::new(),
+            rgba_frame: Vec::new(),
+            field_frame: Vec::new(),
+            onion_frame: Vec::new(),
+            motion_accum: Vec::new(),
+            blur_frame: Vec::new(),
+            blur_line: Vec::new(),
+            recompute_layout: true,
+            layout_computed: false,
+            layout_generation: 0,
+            last_frame: None,
+            initialized: false,
+            loaded: false,
+        }
+    }
+}
+
+impl frei0r_rs2::SourcePlugin for L0ttiePlugin {
+    fn update_source(&mut self, time: f64, outframe: &mut [u32]) {
+        if let Err(err) = self.render_frame(time, outframe) {
+            self.report_error(format!("{err:?}"));
+        }
+        self.config.take();
+    }
+}
+
+impl L0ttiePlugin {
+    /// Construct a plugin for the Lottie JSON `data`, without reading any file.
+    /// Parameters that affect loading (e.g. `background_color`) must be set
+    /// on a plugin loaded from a path instead.
+    pub fn from_json_bytes(width: usize, height: usize, data: &[u8]) -> anyhow::Result<Self> {
+        Self::from_source(
+            width,
+            height,
+            source::Source {
+                data: data.to_vec(),
+                format: source::Format::Json,
+            },
+        )
+    }
+
+    /// Construct a plugin for the active animation of the dotLottie archive `data`,
+    /// without reading any file.
+    pub fn from_dotlottie_bytes(width: usize, height: usize, data: &[u8]) -> anyhow::Result<Self> {
+        Self::from_source(
+            width,
+            height,
+            source::Source {
+                data: data.to_vec(),
+                format: source::Format::DotLottie,
+            },
+        )
+    }
+
+    /// The writable parameters as a JSON object keyed by parameter name,
+    /// which `apply_config` accepts to configure another plugin the same way.
+    pub fn config_string(&self) -> String {
+        config::to_string(self, <Self as frei0r_rs2::Plugin>::PARAMS, READ_ONLY_PARAMS)
+    }
+
+    /// Set the parameters in a `config_string` JSON object, leaving others unchanged.
+    pub fn apply_config(&mut self, config: &str) -> anyhow::Result<()> {
+        self.config.take();
+        config::apply(
+            self,
+            <Self as frei0r_rs2::Plugin>::PARAMS,
+            READ_ONLY_PARAMS,
+            config,
+        )
+    }
+
+    fn from_source(width: usize, height: usize, source: source::Source) -> anyhow::Result<Self> {
+        let mut plugin = <Self as frei0r_rs2::Plugin>::new(width, height);
+        plugin.initialized = true;
+        plugin
+            .load_source("<memory>", source)
+            .context("Failed to load animation data")?;
+        Ok(plugin)
+    }
+
+    /// Render the frame at host `time` (before `time_scale`) into `outframe`,
+    /// a `width * height` buffer of premultiplied ABGR8888 pixels.
+    pub fn render_frame(&mut self, time: f64, outframe: &mut [u32]) -> anyhow::Result<()> {
+        let (width, height) = (self.width, self.height);
+        let sized = if outframe.len() == width * height {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "Output buffer has {} pixels, expected {width}x{height}",
+                outframe.len(),
+            ))
+        };
+        if sized.is_ok() && !self.linear && !self.visible(time) {
+            match self.region() {
+                Some([x, y, w, h]) => {
+                    for row in outframe.chunks_exact_mut(width).skip(y).take(h) {
+                        row[x..x + w].fill(0);
+                    }
+                }
+                None => outframe.fill(0),
+            }
+            return Ok(());
+        }
+        let result = sized.and_then(|_| match self.region() {
+            Some(region) => self.render_region(time, outframe, region),
+            None => self.render_output(time, outframe),
+        });
+        self.status = status::Status::after_render(
+            self.initialized,
+            self.loaded,
+            result.is_ok() && self.consecutive_draw_errors == 0,
+        );
+        result
+    }
+
+    // Whether host `time` is inside the visible_start/visible_end window
+    fn visible(&self, time: f64) -> bool {
+        time >= self.visible_start && (self.visible_end <= 0.0 || time <= self.visible_end)
+    }
+
+    // Output rect `[x, y, width, height]` clipped to the frame, if enabled
+    fn region(&self) -> Option<[usize; 4]> {
+        let [x, y, w, h] = [self.region_x, self.region_y, self.region_w, self.region_h]
+            .map(|v| v.max(0.0) as usize);
+        let (w, h) = (
+            w.min(self.width.saturating_sub(x)),
+            h.min(self.height.saturating_sub(y)),
+        );
+        (w > 0 && h > 0).then_some([x, y, w, h])
+    }
+
+    // Render at the region size and copy it into `outframe`, leaving the rest untouched
+    fn render_region(
+        &mut self,
+        time: f64,
+        outframe: &mut [u32],
+        [x, y, w, h]: [usize; 4],
+    ) -> anyhow::Result<()> {
+        let (width, height) = (self.width, self.height);
+        let mut frame = std::mem::take(&mut self.region_frame);
+        frame.resize(w * h, 0);
+        (self.width, self.height) = (w, h);
+        let result = self.render_output(time, &mut frame);
+        (self.width, self.height) = (width, height);
+        for (row, region_row) in outframe
+            .chunks_exact_mut(width)
+            .skip(y)
+            .zip(frame.chunks_exact(w))
+        {
+            row[x..x + w].copy_from_slice(region_row);
+        }
+        self.region_frame = frame;
+        result
+    }
+
+    fn render_output(&mut self, time: f64, outframe: &mut [u32]) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            outframe.len() == self.width * self.height,
+            "Output buffer has {} pixels, expected {}x{}",
+            outframe.len(),
+            self.width,
+            self.height
+        );
+        if self.reset {
+            self.reset();
+        } else if self.initialized && !self.loaded && self.retry_load() {
+            self.unload();
+        }
+        if !self.initialized && !self.renderer_config.is_empty() {
+            self.configure_renderer();
+        }
+        self.set_target(outframe)
+            .context("Failed to set render target")?;
+        let initialized = if self.initialized {
+            Ok(())
+        } else {
+            self.load()
+        };
+        if !self.loaded {
+            let background = self.background().map(|[r, g, b, _]| [r, g, b]);
+            self.fallback.fill(outframe, self.width, background);
+            return initialized;
+        }
+
+        let time = if self.linear {
+            time
+        } else if self.frame_lock {
+            // One animation frame per rendered call, direction comes from mode
+            self.playhead
+        } else if self.delta_time {
+            // time is the increment since the previous call
+            self.playhead += time * self.time_scale();
+            self.playhead
+        } else if self.realtime {
+            let start = *self.realtime_start.get_or_insert_with(Instant::now);
+            start.elapsed().as_secs_f64() * self.time_scale()
+        } else {
+            self.animation_time(time)
+        };
+        self.current_time = time;
+        if self.rebuild_background {
+            self.rebuild_background()?;
+        }
+        let (start, frames, duration) = self.playback_range()?;
+        let duration = self.dwell_duration(start, frames, duration);
+        self.finished = self.mode.is_finished(time, duration as f32);
+        if self.reuse_last_render(time)? {
+            outframe.copy_from_slice(&self.last_good_frame);
+            return Ok(());
+        }
+        if self.frame_lock && !self.linear {
+            self.playhead += self.frame_duration()?;
+        }
+        self.render(time, outframe).context("Failed to render")?;
+        if self.sample_background && self.consecutive_draw_errors == 0 {
+            self.sample_background = false;
+            if let Some(color) = color::opaque_color(outframe.first().copied().unwrap_or(0)) {
+                self.auto_background_color = Some(color);
+                self.rebuild_background()?;
+                self.render(time, outframe).context("Failed to render")?;
+            }
+        }
+        if self.consecutive_draw_errors > 0 {
+            if self.last_good_frame.len() == outframe.len() {
+                outframe.copy_from_slice(&self.last_good_frame);
+            }
+        } else {
+            if self.background_gradient != gradient::Gradient::None {
+                self.composite_gradient(outframe);
+            }
+            if self.vignette_strength > 0.0 {
+                self.vignette.prepare(
+                    self.width,
+                    self.height,
+                    self.vignette_strength as f32,
+                    self.vignette_radius as f32,
+                );
+            }
+            self.postprocess(outframe);
+            if self.burn_in {
+                let frame = match self.frame_number(time)? {
+                    Some(frame_number) => format!("{frame_number:.1}"),
+                    None => "-".to_owned(),
+                };
+                burnin::draw(
+                    outframe,
+                    self.width,
+                    self.height,
+                    &format!("F {frame} T {time:.2}"),
+                );
+            }
+            if self.debug_bounds
+                && let Some(rect) = self.animation_rect
+            {
+                guides::outline(outframe, self.width, self.height, rect);
+            }
+            if self.safe_guides {
+                guides::draw(outframe, self.width, self.height);
+            }
+            if self.flip_output {
+                mirror::flip_rows(outframe, self.width);
+            }
+            self.last_good_frame.clear();
+            self.last_good_frame.extend_from_slice(outframe);
+            if self.max_fps > 0.0 {
+                self.last_render = Some((Instant::now(), self.frame_number(time)?));
+            }
+        }
+        Ok(())
+    }
+
+    // With max_fps, reuse the last frame if it was rendered recently and is unchanged
+    fn reuse_last_render(&self, time: f64) -> anyhow::Result<bool> {
+        let Some((instant, frame_number)) = self.last_render else {
+            return Ok(false);
+        };
+        if self.max_fps <= 0.0
+            || self.recompute_layout
+            || self.last_good_frame.len() != self.width * self.height
+            || instant.elapsed().as_secs_f64() >= 1.0 / self.max_fps
+        {
+            return Ok(false);
+        }
+        Ok(self.frame_number(time)? == frame_number)
+    }
+
+    /// Render the frame at host `time` (before `time_scale`) into `out`, a
+    /// `width * height * 4` buffer of premultiplied 16-bit RGBA values. ThorVG
+    /// renders only 8 bits per channel, so this upsamples the 8-bit render with
+    /// ordered dithering to hide banding rather than adding precision.
+    pub fn render_frame_u16(&mut self, time: f64, out: &mut [u16]) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            out.len() == self.width * self.height * 4,
+            "Output buffer has {} values, expected {}x{}x4",
+            out.len(),
+            self.width,
+            self.height
+        );
+        let mut frame = std::mem::take(&mut self.rgba_frame);
+        frame.resize(self.width * self.height, 0);
+        let result = self.render_frame(time, &mut frame);
+        if result.is_ok() {
+            for (i, (rgba, pixel)) in out.chunks_exact_mut(4).zip(&frame).enumerate() {
+                let (x, y) = (i % self.width, i / self.width);
+                rgba.copy_from_slice(&dither::upsample(pixel::unpack(*pixel), x, y));
+            }
+        }
+        self.rgba_frame = frame;
+        result
+    }
+
+    /// Move the playhead to host time `time` (before `time_scale`) without rendering.
+    pub fn set_time(&mut self, time: f64) {
+        self.current_time = self.animation_time(time);
+    }
+
+    // Animation time at host `time`, through time_map if set, else time_scale
+    fn animation_time(&self, time: f64) -> f64 {
+        match &self.time_map {
+            Some(time_map) => time_map.time(time),
+            None => time * self.time_scale(),
+        }
+    }
+
+    /// Frame number at the playhead, resolved through `mode`, `loop` and the other
+    /// playback parameters as a render would. 0 if no animation is loaded.
+    pub fn current_frame(&self) -> f32 {
+        self.frame_number(self.current_time)
+            .ok()
+            .flatten()
+            .unwrap_or(0.0)
+    }
+
+    /// Render the frame at host `time` (before `time_scale`) into `out`,
+    /// a `width * height * 4` buffer of premultiplied RGBA bytes.
+    pub fn render_frame_rgba8(&mut self, time: f64, out: &mut [u8]) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            out.len() == self.width * self.height * 4,
+            "Output buffer has {} bytes, expected {}x{}x4",
+            out.len(),
+            self.width,
+            self.height
+        );
+        let mut frame = std::mem::take(&mut self.rgba_frame);
+        frame.resize(self.width * self.height, 0);
+        let result = self.render_frame(time, &mut frame);
+        if result.is_ok() {
+            for (rgba, pixel) in out.chunks_exact_mut(4).zip(&frame) {
+                rgba.copy_from_slice(&pixel::unpack(*pixel));
+            }
+        }
+        self.rgba_frame = frame;
+        result
+    }
+
+    /// Render the frame at host `time` (before `time_scale`) as a fill and key pair:
+    /// `rgb` receives opaque straight color ABGR8888 pixels (black where transparent)
+    /// and `alpha` the alpha channel, both `width * height` long.
+    pub fn render_frame_split(
+        &mut self,
+        time: f64,
+        rgb: &mut [u32],
+        alpha: &mut [u8],
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            alpha.len() == self.width * self.height,
+            "Alpha buffer has {} bytes, expected {}x{}",
+            alpha.len(),
+            self.width,
+            self.height
+        );
+        self.render_frame(time, rgb)?;
+        for (pixel, alpha) in rgb.iter_mut().zip(alpha.iter_mut()) {
+            *alpha = pixel::unpack(*pixel)[3];
+            *pixel = pixel::pack(color::opaque_color(*pixel).unwrap_or([0, 0, 0, 255]));
+        }
+        Ok(())
+    }
+
+    /// Render the frame at host `time` (before `time_scale`) into a newly allocated
+    /// `width * height` buffer of premultiplied ABGR8888 pixels.
+    pub fn render_to_vec(&mut self, time: f64) -> anyhow::Result<Vec<u32>> {
+        let mut frame = vec![0; self.width * self.height];
+        self.render_frame(time, &mut frame)?;
+        Ok(frame)
+    }
+
+    /// Render every frame from 0 to the animation (or `marker`) duration at `fps`
+    /// frames per second. Playback parameters such as `mode`, `loop`, `time_scale`,
+    /// `max_frame` and `visible_start`/`visible_end` are ignored for this linear pass. Iteration stops after the first error.
+    pub fn frames(&mut self, fps: f64) -> impl Iterator<Item = anyhow::Result<Vec<u32>>> + '_ {
+        self.linear = true;
+        Frames {
+            plugin: self,
+            fps,
+            index: 0,
+            done: fps <= 0.0,
+        }
+    }
+
+    // Discard the loaded animation and render state so the next render reloads
+    fn reset(&mut self) {
+        self.reset = false;
+        self.unload();
+        self.load_failures = 0;
+        self.failed_source = None;
+        self.last_render = None;
+        self.playhead = 0.0;
+        self.last_good_frame.clear();
+        self.draw_errors = 0;
+        self.consecutive_draw_errors = 0;
+        self.anim_width = 0.0;
+        self.anim_height = 0.0;
+        self.anim_name = CString::default();
+        self.animation_count = 0;
+        self.markers.clear();
+        self.goto_frame = None;
+        self.content_bounds = None;
+        self.marker_range = None;
+        self.anim_duration = 0.0;
+        self.anim_total_frames = 0.0;
+        self.finished = false;
+        self.last_error = CString::default();
+    }
+
+    // Rebuild the (not yet initialized) renderer with renderer_config options
+    fn configure_renderer(&mut self) {
+        let (config, warnings) =
+            engine::EngineConfig::parse(&self.renderer_config.to_string_lossy());
+        for warning in warnings {
+            self.report_error(warning);
+        }
+        self.renderer =
+            dotlottie_rs::TvgRenderer::new(dotlottie_rs::TvgEngine::TvgEngineSw, config.threads);
+    }
+
+    // Discard the renderer and animation so the next render initializes again
+    fn unload(&mut self) {
+        // Drop the renderer before the drawables it references
+        self.renderer = dotlottie_rs::TvgRenderer::new(dotlottie_rs::TvgEngine::TvgEngineSw, 0);
+        self.animation = dotlottie_rs::TvgAnimation::default();
+        self.background_shape = None;
+        self.border_shape = None;
+        self.bar_shape = None;
+        self.decor = None;
+        self.initialized = false;
+        self.loaded = false;
+        self.recompute_layout = true;
+        self.layout_computed = false;
+        self.last_frame = None;
+        self.realtime_start = None;
+        self.frame_cache.clear();
+        self.status = status::Status::Uninitialized;
+    }
+
+    fn load(&mut self) -> anyhow::Result<()> {
+        let result = self.initialize().context("Failed to initialize plugin");
+        if result.is_ok() {
+            self.load_failures = 0;
+            self.failed_source = None;
+        } else {
+            self.load_failures += 1;
+            self.failed_source = Some(self.source_key());
+            if self.load_failures == MAX_LOAD_FAILURES {
+                self.log(
+                    "warn",
+                    &format!(
+                        "Failed to load {:?} {MAX_LOAD_FAILURES} times, not retrying until it changes",
+                        self.animation_path
+                    ),
+                );
+            }
+        }
+        result
+    }
+
+    // Path and modification time identifying the animation source
+    fn source_key(&self) -> (CString, Option<SystemTime>) {
+        if !self.animation_json.is_empty() {
+            return (self.animation_json.clone(), None);
+        }
+        let Ok(path) = self.animation_path.to_str() else {
+            return (self.animation_path.clone(), None);
+        };
+        let path = source::resolve(path, &self.base_dir.to_string_lossy());
+        let modified = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        (CString::new(path).unwrap_or_default(), modified)
+    }
+
+    // Retry a failed load a few times, then only once the path or file changes
+    fn retry_load(&mut self) -> bool {
+        if self.load_failures == 0 {
+            return false;
+        }
+        if self.load_failures < MAX_LOAD_FAILURES {
+            return true;
+        }
+        if self.failed_source.as_ref() != Some(&self.source_key()) {
+            self.load_failures = 0;
+            return true;
+        }
+        false
+    }
+
+    // background_rgba if set, otherwise the opaque background_color
+    fn background(&self) -> Option<[u8; 4]> {
+        if self.background_gradient != gradient::Gradient::None {
+            return None;
+        }
+        if let Some(color) = self.auto_background_color.filter(|_| self.auto_background) {
+            return Some(color);
+        }
+        self.background_rgba.or_else(|| {
+            self.background_color.map(|color| {
+                let [r, g, b] = [color.r, color.g, color.b].map(|c| (c * 255.0) as u8);
+                [r, g, b, 255]
+            })
+        })
+    }
+
+    fn log(&self, level: &str, message: &str) {
+        if self.json_logs {
+            let path = self.animation_path.to_string_lossy();
+            eprintln!(
+                "{}",
+                logging::json_record(level, message, &path, self.last_frame)
+            );
+        } else {
+            eprintln!("{message}");
+        }
+    }
+
+    fn report_error(&mut self, message: String) {
+        self.log("error", &message);
+        self.last_error = CString::new(message).unwrap_or_default();
+    }
+
+    fn replace_text(&mut self, data: String) -> anyhow::Result<String> {
+        let spec = self
+            .text_replacements
+            .to_str()
+            .context("Invalid UTF-8 in text_replacements")?
+            .to_owned();
+        let replacements = text::parse(&spec)?;
+        if replacements.is_empty() {
+            return Ok(data);
+        }
+        let (data, unknown) = text::replace(&data, &replacements)?;
+        if !unknown.is_empty() {
+            self.report_error(format!("No text layers named: {}", unknown.join(", ")));
+        }
+        Ok(data)
+    }
+
+    // Multiplier from host time to animation time. With fps_hint, time_scale
+    // is in animation frames per host frame: time_scale * fps_hint / animation fps
+    fn time_scale(&self) -> f64 {
+        let time_scale = if self.max_time_scale > 0.0 {
+            self.time_scale
+                .clamp(-self.max_time_scale, self.max_time_scale)
+        } else {
+            self.time_scale
+        };
+        if self.fps_hint > 0.0 && self.anim_total_frames > 0.0 && self.anim_duration > 0.0 {
+            time_scale * self.fps_hint * self.anim_duration / self.anim_total_frames
+        } else {
+            time_scale
+        }
+    }
+
+    // ABGR8888 packs pixels as 0xAABBGGRR, which frei0r's RGBA8888 byte order
+    // matches on little-endian hosts. ThorVG has no colorspace with RGBA bytes
+    // on big-endian hosts, where channels would be reversed.
+    fn set_target(&mut self, buffer: &mut [u32]) -> anyhow::Result<()> {
+        self.renderer.set_target(
+            buffer,
+            self.width as u32,
+            self.width as u32,
+            self.height as u32,
+            ColorSpace::ABGR8888,
+        )?;
+        Ok(())
+    }
+
+    fn initialize(&mut self) -> anyhow::Result<()> {
+        self.initialized = true;
+        if !self.animation_json.is_empty() {
+            let source = source::Source {
+                data: self.animation_json.as_bytes().to_vec(),
+                format: source::Format::Json,
+            };
+            return self.load_source("<animation_json>", source);
+        }
+        #[cfg(feature = "embedded_animation")]
+        if self.animation_path.is_empty() {
+            let source = source::Source {
+                data: EMBEDDED_ANIMATION.as_bytes().to_vec(),
+                format: source::Format::Json,
+            };
+            return self.load_source("<embedded>", source);
+        }
+        let animation_path = self
+            .animation_path
+            .to_str()
+            .with_context(|| format!("Invalid lottie animation path: {:?}", self.animation_path))?;
+        let animation_path = source::resolve(animation_path, &self.base_dir.to_string_lossy());
+        let animation_path = animation_path.as_str();
+
+        let cache_ttl = Duration::try_from_secs_f64(self.cache_ttl).unwrap_or_default();
+        let retry = source::Retry {
+            retries: self.read_retries.max(0.0) as u32,
+            delay: Duration::try_from_secs_f64(self.read_retry_delay_ms / 1000.0)
+                .unwrap_or_default(),
+        };
+        let (source, warnings) = source::load(animation_path, cache_ttl, retry)?;
+        for warning in warnings {
+            self.log("warn", &warning);
+        }
+        self.load_source(animation_path, source)
+    }
+
+    // Load the animation from `source`, `animation_path` identifies it in errors
+    fn load_source(&mut self, animation_path: &str, source: source::Source) -> anyhow::Result<()> {
+        self.status = status::Status::Loading;
+        let data = match source.format {
+            source::Format::Json => {
+                self.animation_count = 1;
+                // A stray invalid byte, e.g. in a metadata string, shouldn't prevent loading
+                String::from_utf8(source.data).unwrap_or_else(|err| {
+                    self.report_error(format!(
+                        "Invalid UTF-8 in lottie animation path, replaced invalid bytes: {animation_path}"
+                    ));
+                    String::from_utf8_lossy(err.as_bytes()).into_owned()
+                })
+            }
+            source::Format::DotLottie => {
+                let index = (self.animation_index >= 0.0).then_some(self.animation_index as usize);
+                let dotlottie = dotlottie::load(&source.data, index).with_context(|| {
+                    format!("Failed to extract dotLottie animation: {animation_path}")
+                })?;
+                self.animation_count = dotlottie.count;
+                if index.is_some_and(|index| index >= dotlottie.count) {
+                    self.report_error(format!(
+                        "dotLottie animation_index {} out of range, {} animations available, using 0",
+                        self.animation_index, dotlottie.count
+                    ));
+                }
+                dotlottie.animation
+            }
+        };
+        let data = self.replace_text(data)?;
+
+        self.animation
+            .load_data(&data, "lottie", true)
+            .with_context(|| format!("Failed to load lottie animation path: {animation_path}"))?;
+        if let Err(err) = self.apply_theme() {
+            self.report_error(format!("{err:?}"));
+        }
+        self.auto_background_color = None;
+        self.sample_background = self.auto_background;
+        // With auto_background the shape starts transparent until sampled,
+        // with a gradient it is empty in case the gradient is turned off
+        if self.background().is_some()
+            || self.auto_background
+            || self.background_gradient != gradient::Gradient::None
+        {
+            let mut background_shape = dotlottie_rs::TvgShape::default();
+            self.shape_background(&mut background_shape)?;
+            self.rebuild_background = false;
+            self.renderer
+                .push(Drawable::Shape(&background_shape))
+                .context("Failed to add background shape")?;
+            self.background_shape = Some(background_shape);
+        }
+        if self.bar_color.is_some() {
+            // Empty until the layout is computed
+            let bar_shape = dotlottie_rs::TvgShape::default();
+            self.renderer
+                .push(Drawable::Shape(&bar_shape))
+                .context("Failed to add bar shape")?;
+            self.bar_shape = Some(bar_shape);
+        }
+        self.renderer
+            .push(Drawable::Animation(&self.animation))
+            .context("Failed to add animation")?;
+        // Empty until the layout is computed
+        let border_shape = dotlottie_rs::TvgShape::default();
+        self.renderer
+            .push(Drawable::Shape(&border_shape))
+            .context("Failed to add border shape")?;
+        self.border_shape = Some(border_shape);
+        // A newly loaded animation starts at frame 0
+        self.last_frame = Some(0.0);
+        let (animation_width, animation_height) = self
+            .animation
+            .get_size()
+            .context("Failed to query animation size")?;
+        self.anim_width = animation_width as f64;
+        self.anim_height = animation_height as f64;
+        self.anim_total_frames = self
+            .animation
+            .get_total_frame()
+            .context("Failed to query total frames")? as f64;
+        self.anim_duration = self.duration()? as f64;
+        let reported = self
+            .animation
+            .get_duration()
+            .context("Failed to query duration")?;
+        if reported <= 0.0 && self.anim_total_frames > 0.0 {
+            self.report_error(format!(
+                "Animation has {} frames but no duration, playing at {FALLBACK_FRAME_RATE} fps",
+                self.anim_total_frames
+            ));
+        }
+        let metadata = metadata::Metadata::parse(&data);
+        self.anim_name = metadata
+            .name
+            .and_then(|name| CString::new(name).ok())
+            .unwrap_or_default();
+        self.markers = metadata.markers;
+        self.content_bounds = self
+            .measure_content(&metadata.layers)
+            .unwrap_or_else(|err| {
+                self.report_error(format!("{err:?}"));
+                None
+            });
+        self.resolve_marker();
+        self.loaded = true;
+        self.resolve_goto_marker();
+        Ok(())
+    }
+
+    // Apply the slot overrides in theme_path, if set
+    fn apply_theme(&mut self) -> anyhow::Result<()> {
+        if self.theme_path.is_empty() {
+            return Ok(());
+        }
+        let path = self
+            .theme_path
+            .to_str()
+            .with_context(|| format!("Invalid theme path: {:?}", self.theme_path))?;
+        let path = source::resolve(path, &self.base_dir.to_string_lossy());
+        let slots = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read theme: {path}"))?;
+        self.animation
+            .set_slots(&slots)
+            .with_context(|| format!("Failed to apply theme: {path}"))?;
+        Ok(())
+    }
+
+    // Build the background rect into `shape`, replacing any previous path
+    fn shape_background(&self, shape: &mut dotlottie_rs::TvgShape) -> anyhow::Result<()> {
+        shape.reset().context("Failed to reset background shape")?;
+        let Some([r, g, b, a]) = self.background().or(self.auto_background.then_some([0; 4]))
+        else {
+            return Ok(());
+        };
+        let radius = self.background_radius.max(0.0) as f32;
+        shape
+            .append_rect(
+                0.0,
+                0.0,
+                self.width as f32,
+                self.height as f32,
+                radius,
+                radius,
+            )
+            .context("Failed to construct background shape")?;
+        shape
+            .fill((r, g, b, a))
+            .context("Failed to fill background shape")?;
+        Ok(())
+    }
+
+    // Composite `outframe` over the background gradient, redrawn when it changes
+    fn composite_gradient(&mut self, outframe: &mut [u32]) {
+        if self.gradient_frame.len() != outframe.len() {
+            self.gradient_frame.resize(outframe.len(), 0);
+            let [start, end] = [&self.gradient_start, &self.gradient_end]
+                .map(|color| [color.r, color.g, color.b].map(|c| (c * 255.0) as u8));
+            self.background_gradient.fill(
+                &mut self.gradient_frame,
+                self.width,
+                self.height,
+                start,
+                end,
+            );
+        }
+        blur::over(outframe, &self.gradient_frame);
+    }
+
+    // Build the border around the `fitted` rect into `shape`, empty if disabled
+    fn shape_border(
+        &self,
+        shape: &mut dotlottie_rs::TvgShape,
+        fitted: [f32; 4],
+    ) -> anyhow::Result<()> {
+        shape.reset().context("Failed to reset border shape")?;
+        let frame = [0.0, 0.0, self.width as f32, self.height as f32];
+        let Some(rects) = fit::intersect(fitted, frame)
+            .and_then(|rect| fit::border_rects(rect, self.border_width as f32))
+        else {
+            return Ok(());
+        };
+        for [x, y, w, h] in rects {
+            shape
+                .append_rect(x, y, w, h, 0.0, 0.0)
+                .context("Failed to construct border shape")?;
+        }
+        let color = &self.border_color;
+        let [r, g, b] = [color.r, color.g, color.b].map(|c| (c * 255.0) as u8);
+        shape
+            .fill((r, g, b, 255))
+            .context("Failed to fill border shape")?;
+        Ok(())
+    }
+
+    // Build the bars outside the `fitted` rect into `shape`
+    fn shape_bars(
+        &self,
+        shape: &mut dotlottie_rs::TvgShape,
+        fitted: [f32; 4],
+    ) -> anyhow::Result<()> {
+        shape.reset().context("Failed to reset bar shape")?;
+        let Some(color) = &self.bar_color else {
+            return Ok(());
+        };
+        let frame = [0.0, 0.0, self.width as f32, self.height as f32];
+        let rects = fit::bar_rects(fitted, frame);
+        if rects.is_empty() {
+            return Ok(());
+        }
+        for [x, y, w, h] in rects {
+            shape
+                .append_rect(x, y, w, h, 0.0, 0.0)
+                .context("Failed to construct bar shape")?;
+        }
+        let [r, g, b] = [color.r, color.g, color.b].map(|c| (c * 255.0) as u8);
+        shape
+            .fill((r, g, b, 255))
+            .context("Failed to fill bar shape")?;
+        Ok(())
+    }
+
+    // Apply a changed background_radius to the already pushed shape
+    fn rebuild_background(&mut self) -> anyhow::Result<()> {
+        self.rebuild_background = false;
+        if self.background_shape.is_some()
+            && let Some(mut decor) = self.decor.take()
+        {
+            let result = self.shape_background(&mut decor.background_shape);
+            decor.redraw = true;
+            self.decor = Some(decor);
+            result?;
+            self.layout_generation += 1;
+        } else if let Some(mut shape) = self.background_shape.take() {
+            let result = self.shape_background(&mut shape);
+            self.background_shape = Some(shape);
+            result?;
+            // Invalidate cached frames drawn with the old shape
+            self.layout_generation += 1;
+        }
+        Ok(())
+    }
+
+    // Union of the drawn `layers` bounds at the current frame, at the declared size
+    fn measure_content(&mut self, layers: &[String]) -> anyhow::Result<Option<[f32; 4]>> {
+        let (animation_width, animation_height) = self.animation.get_size()?;
+        self.animation.set_size(animation_width, animation_height)?;
+        self.animation.translate(0.0, 0.0)?;
+        self.renderer
+            .update()
+            .context("Failed to update animation bounds")?;
+        // Layers ThorVG can't find are skipped
+        let bounds: Vec<_> = layers
+            .iter()
+            .filter_map(|layer| self.animation.get_layer_bounds(layer).ok())
+            .collect();
+        self.recompute_layout = true;
+        Ok(fit::union_bounds(&bounds))
+    }
+
+    // Resolve the marker param to marker_range, or to the span of all markers
+    // with markers_span when no marker is named
+    fn resolve_marker(&mut self) {
+        let name = self.marker.to_string_lossy().into_owned();
+        self.marker_range = None;
+        if name.is_empty() {
+            if self.markers_span {
+                self.marker_range = metadata::span(&self.markers);
+            }
+            return;
+        }
+        match self.markers.iter().find(|marker| marker.name == name) {
+            Some(marker) => self.marker_range = Some((marker.start, marker.frames)),
+            None => self.report_error(format!("Unknown marker {name}, playing the full animation")),
+        }
+    }
+
+    fn resolve_goto_marker(&mut self) {
+        self.goto_frame = None;
+        if self.goto_marker < 0.0 || !self.loaded {
+            return;
+        }
+        let Some(last) = self.markers.len().checked_sub(1) else {
+            self.report_error("goto_marker is set but the animation has no markers".to_owned());
+            return;
+        };
+        let index = self.goto_marker as usize;
+        if index > last {
+            self.report_error(format!(
+                "goto_marker {index} out of range, using the last of {} markers",
+                self.markers.len()
+            ));
+        }
+        self.goto_frame = Some(self.markers[index.min(last)].start);
+    }
+
+    // Start frame, frame count and duration of the range being played,
+    // the selected marker or the full animation
+    fn playback_range(&self) -> anyhow::Result<(f32, f32, f32)> {
+        let duration = self.duration()?;
+        let total_frames = self
+            .animation
+            .get_total_frame()
+            .context("Failed to query total frames")?;
+        Ok(match self.marker_range {
+            Some((start, frames)) if total_frames > 0.0 => {
+                (start, frames, frames * duration / total_frames)
+            }
+            _ => (0.0, total_frames, duration),
+        })
+    }
+
+    // Crop rectangle in animation coordinates, if enabled
+    fn crop(&self) -> Option<[f32; 4]> {
+        (self.crop_w > 0.0 && self.crop_h > 0.0)
+            .then_some([self.crop_x, self.crop_y, self.crop_w, self.crop_h].map(|v| v as f32))
+    }
+
+    // Layout transform for `fit`, or for fit_x/fit_y with `fit` filling in an unset axis
+    fn layout_transform(
+        &self,
+        width: f32,
+        height: f32,
+        animation_width: f32,
+        animation_height: f32,
+    ) -> (f32, f32, f32, f32) {
+        if self.fit_x.is_none() && self.fit_y.is_none() {
+            if let Some(transform) = fit::exact_transform(
+                self.layout.fit,
+                [width, height],
+                [animation_width, animation_height],
+            ) {
+                return transform;
+            }
+            return self.layout.compute_layout_transform(
+                width,
+                height,
+                animation_width,
+                animation_height,
+            );
+        }
+        fit::per_axis_transform(
+            self.fit_x.unwrap_or(self.layout.fit),
+            self.fit_y.unwrap_or(self.layout.fit),
+            &self.layout.align,
+            [width, height],
+            [animation_width, animation_height],
+        )
+    }
+
+    // Apply the layout transform, or a cover transform if `cover`
+    fn compute_layout(&mut self, cover: bool) -> anyhow::Result<()> {
+        let (animation_width, animation_height) = self.animation.get_size()?;
+        // Lay out in display space, then map horizontally back to storage pixels
+        let pixel_aspect = if self.pixel_aspect > 0.0 {
+            self.pixel_aspect as f32
+        } else {
+            1.0
+        };
+        let display_width = self.width as f32 * pixel_aspect;
+        let (sx, sy, tx, ty) = if cover {
+            dotlottie_rs::Layout::new(dotlottie_rs::Fit::Cover, self.layout.align.clone())
+                .compute_layout_transform(
+                    display_width,
+                    self.height as f32,
+                    animation_width,
+                    animation_height,
+                )
+        } else if let Some(crop) = self.crop() {
+            let crop_layout =
+                self.layout_transform(display_width, self.height as f32, crop[2], crop[3]);
+            fit::content_transform(crop, animation_width, animation_height, crop_layout)
+        } else if self.native_size {
+            fit::native_transform(
+                display_width,
+                self.height as f32,
+                animation_width,
+                animation_height,
+            )
+        } else if self.fit_content
+            && let Some(content) = self.content_bounds
+        {
+            let content_layout =
+                self.layout_transform(display_width, self.height as f32, content[2], content[3]);
+            fit::content_transform(content, animation_width, animation_height, content_layout)
+        } else {
+            self.layout_transform(
+                display_width,
+                self.height as f32,
+                animation_width,
+                animation_height,
+            )
+        };
+        let (sx, tx) = (sx / pixel_aspect, tx / pixel_aspect);
+        let (sx, sy, tx, ty) = if self.snap_to_pixels {
+            fit::snap((sx, sy, tx, ty))
+        } else {
+            (sx, sy, tx, ty)
+        };
+        if !cover {
+            self.animation_rect = Some([tx, ty, sx, sy]);
+            let transform = (sx, sy, tx, ty);
+            self.crop_clip = self
+                .crop()
+                .map(|crop| fit::map_rect(crop, transform, animation_width, animation_height));
+            let region = self
+                .crop()
+                .or(self
+                    .content_bounds
+                    .filter(|_| self.fit_content && !self.native_size))
+                .unwrap_or([0.0, 0.0, animation_width, animation_height]);
+            let fitted = fit::map_rect(region, transform, animation_width, animation_height);
+            if let Some(mut shape) = self.border_shape.take() {
+                let result = self.shape_border(&mut shape, fitted);
+                self.border_shape = Some(shape);
+                result?;
+            }
+            self.shape_decor(fitted)?;
+        }
+        self.cover_layout = cover;
+        self.animation.set_size(sx, sy)?;
+        self.animation.translate(tx, ty)?;
+        self.layout_generation += 1;
+        Ok(())
+    }
+
+    // Shape the background and bars around the `fitted` rect. While crop_clip is set
+    // they move to `decor` and the main scene shapes are left empty.
+    fn shape_decor(&mut self, fitted: [f32; 4]) -> anyhow::Result<()> {
+        let separate = self.crop_clip.is_some()
+            && (self.background_shape.is_some() || self.bar_shape.is_some());
+        let mut background = self.background_shape.take();
+        let mut bars = self.bar_shape.take();
+        let result = if separate {
+            self.shape_separate_decor(fitted, background.as_mut(), bars.as_mut())
+        } else {
+            let restore = match (self.decor.take(), background.as_mut()) {
+                (Some(_), Some(shape)) => self.shape_background(shape),
+                _ => Ok(()),
+            };
+            restore.and_then(|_| match bars.as_mut() {
+                Some(shape) => self.shape_bars(shape, fitted),
+                None => Ok(()),
+            })
+        };
+        self.background_shape = background;
+        self.bar_shape = bars;
+        result
+    }
+
+    fn shape_separate_decor(
+        &mut self,
+        fitted: [f32; 4],
+        background: Option<&mut dotlottie_rs::TvgShape>,
+        bars: Option<&mut dotlottie_rs::TvgShape>,
+    ) -> anyhow::Result<()> {
+        // Only draw a background if the animation was loaded with one
+        let has_background = background.is_some();
+        if let Some(shape) = background {
+            shape.reset().context("Failed to reset background shape")?;
+        }
+        if let Some(shape) = bars {
+            shape.reset().context("Failed to reset bar shape")?;
+        }
+        let mut decor = match self.decor.take() {
+            Some(decor) => decor,
+            None => {
+                let mut decor = Decor {
+                    renderer: dotlottie_rs::TvgRenderer::new(
+                        dotlottie_rs::TvgEngine::TvgEngineSw,
+                        0,
+                    ),
+                    background_shape: dotlottie_rs::TvgShape::default(),
+                    bar_shape: dotlottie_rs::TvgShape::default(),
+                    frame: Vec::new(),
+                    redraw: true,
+                };
+                if has_background {
+                    self.shape_background(&mut decor.background_shape)?;
+                }
+                decor
+                    .renderer
+                    .push(Drawable::Shape(&decor.background_shape))
+                    .context("Failed to add background shape")?;
+                decor
+                    .renderer
+                    .push(Drawable::Shape(&decor.bar_shape))
+                    .context("Failed to add bar shape")?;
+                decor
+            }
+        };
+        let result = self.shape_bars(&mut decor.bar_shape, fitted);
+        decor.redraw = true;
+        self.decor = Some(decor);
+        result
+    }
+
+    // Clear the animation outside crop_clip, then composite it over the decor
+    fn composite_crop(&mut self, buffer: &mut [u32]) -> anyhow::Result<()> {
+        if let Some(clip) = self.crop_clip.filter(|_| !self.cover_layout) {
+            mask::clip_rect(buffer, self.width, self.height, clip);
+        }
+        let Some(decor) = &mut self.decor else {
+            return Ok(());
+        };
+        if decor.redraw || decor.frame.len() != buffer.len() {
+            decor.frame.clear();
+            decor.frame.resize(buffer.len(), 0);
+            decor.renderer.set_target(
+                &mut decor.frame,
+                self.width as u32,
+                self.width as u32,
+                self.height as u32,
+                ColorSpace::ABGR8888,
+            )?;
+            decor.renderer.update()?;
+            decor.renderer.draw(true)?;
+            decor.renderer.sync()?;
+            decor.redraw = false;
+        }
+        blur::over(buffer, &decor.frame);
+        Ok(())
+    }
+
+    fn render(&mut self, time: f64, outframe: &mut [u32]) -> anyhow::Result<()> {
+        if self.align_y_anim {
+            self.layout.align[1] = fit::pan_align(time, self.pan_speed);
+            self.recompute_layout = true;
+        }
+        // Pending layout changes are applied once lock_layout is turned off
+        if self.recompute_layout && !(self.lock_layout && self.layout_computed) {
+            self.compute_layout(false)
+                .context("Failed to compute layout")?;
+            self.recompute_layout = false;
+            self.layout_computed = true;
+        }
+
+        if self.onion_skin != 0.0 {
+            self.render_onion_skin(time, outframe)
+        } else {
+            self.render_content(time, outframe)
+        }
+    }
+
+    fn render_content(&mut self, time: f64, outframe: &mut [u32]) -> anyhow::Result<()> {
+        if self.blur_fill {
+            self.render_blur_fill(time, outframe)
+        } else {
+            self.render_layout(time, outframe)
+        }
+    }
+
+    // Render the frame onion_skin frames earlier faded, then the current frame over it
+    fn render_onion_skin(&mut self, time: f64, outframe: &mut [u32]) -> anyhow::Result<()> {
+        let earlier = time - self.onion_skin.round() * self.frame_duration()?;
+        let mut ghost = std::mem::take(&mut self.onion_frame);
+        ghost.resize(self.width * self.height, 0);
+        let result = self
+            .set_target(&mut ghost)
+            .and_then(|_| self.render_content(earlier, &mut ghost));
+        let target = self.set_target(outframe);
+        let result = result
+            .and(target)
+            .and_then(|_| self.render_content(time, outframe));
+        if result.is_ok() {
+            color::fade(&mut ghost, ONION_SKIN_OPACITY);
+            blur::over(outframe, &ghost);
+        }
+        self.onion_frame = ghost;
+        result
+    }
+
+    fn render_layout(&mut self, time: f64, outframe: &mut [u32]) -> anyhow::Result<()> {
+        if self.interlaced {
+            self.render_interlaced(time, outframe)?;
+        } else {
+            self.render_progressive(time, outframe)?;
+        }
+        self.composite_crop(outframe)
+            .context("Failed to composite crop")
+    }
+
+    // Render a blurred cover layout behind the fitted layout
+    fn render_blur_fill(&mut self, time: f64, outframe: &mut [u32]) -> anyhow::Result<()> {
+        let mut back = std::mem::take(&mut self.blur_frame);
+        back.resize(self.width * self.height, 0);
+        let result = self
+            .compute_layout(true)
+            .and_then(|_| self.set_target(&mut back))
+            .and_then(|_| self.render_layout(time, &mut back));
+        let layout = self.compute_layout(false);
+        let target = self.set_target(outframe);
+        let result = result
+            .and(layout)
+            .and(target)
+            .and_then(|_| self.render_layout(time, outframe));
+        if result.is_ok() {
+            let radius = (self.width.max(self.height) / BLUR_FILL_DIVISOR).max(1);
+            blur::box_blur(
+                &mut back,
+                self.width,
+                self.height,
+                radius,
+                &mut self.blur_line,
+            );
+            blur::over(outframe, &back);
+        }
+        self.blur_frame = back;
+        result
+    }
+
+    fn render_progressive(&mut self, time: f64, outframe: &mut [u32]) -> anyhow::Result<()> {
+        if self.channel_phase != 0.0 {
+            self.render_channel_phase(time, outframe)
+        } else {
+            self.render_sampled(time, outframe)
+        }
+    }
+
+    // Seconds into the playback range of the marker starts marker_dwell pauses at, sorted
+    fn dwell_stops(&self, start: f32, frames: f32, duration: f32) -> Vec<f64> {
+        if self.marker_dwell <= 0.0
+            || frames <= 0.0
+            || !matches!(self.mode, mode::Mode::Forward | mode::Mode::Once)
+        {
+            return Vec::new();
+        }
+        let mut stops: Vec<f64> = self
+            .markers
+            .iter()
+            .filter(|marker| (start..start + frames).contains(&marker.start))
+            .map(|marker| ((marker.start - start) / frames * duration) as f64)
+            .collect();
+        stops.sort_by(f64::total_cmp);
+        stops
+    }
+
+    // Playback range duration including the marker_dwell pauses
+    fn dwell_duration(&self, start: f32, frames: f32, duration: f32) -> f64 {
+        let stops = self.dwell_stops(start, frames, duration);
+        dwell::extended_duration(duration as f64, &stops, self.marker_dwell)
+    }
+
+    // Remap animation `time` to pause for marker_dwell at each marker start within
+    // the playback range, repeating the pauses every pass when looping
+    fn dwell_time(&self, time: f64, start: f32, frames: f32, duration: f32) -> f64 {
+        let stops = self.dwell_stops(start, frames, duration);
+        if stops.is_empty() {
+            return time;
+        }
+        let looping = matches!(self.mode, mode::Mode::Forward)
+            && (self.loop_animation || self.edge == edge::Edge::Loop);
+        let time = if looping {
+            time.rem_euclid(dwell::extended_duration(
+                duration as f64,
+                &stops,
+                self.marker_dwell,
+            ))
+        } else {
+            time
+        };
+        dwell::remap(time, &stops, self.marker_dwell)
+    }
+
+    // Duration of the animation in seconds, see effective_duration
+    fn duration(&self) -> anyhow::Result<f32> {
+        let duration = self
+            .animation
+            .get_duration()
+            .context("Failed to query duration")?;
+        let total_frames = self
+            .animation
+            .get_total_frame()
+            .context("Failed to query total frames")?;
+        Ok(effective_duration(duration, total_frames))
+    }
+
+    // Duration of one animation frame in seconds
+    fn frame_duration(&self) -> anyhow::Result<f64> {
+        let duration = self.duration()?;
+        let total_frames = self
+            .animation
+            .get_total_frame()
+            .context("Failed to query total frames")?;
+        Ok(if total_frames > 0.0 {
+            (duration / total_frames) as f64
+        } else {
+            0.0
+        })
+    }
+
+    // Render the first field into outframe and weave in the second field's rows
+    // rendered half an animation frame later
+    fn render_interlaced(&mut self, time: f64, outframe: &mut [u32]) -> anyhow::Result<()> {
+        let half_frame = 0.5 * self.frame_duration()?;
+        self.render_progressive(time, outframe)?;
+
+        let mut field = std::mem::take(&mut self.field_frame);
+        field.resize(self.width * self.height, 0);
+        let result = self
+            .set_target(&mut field)
+            .and_then(|_| self.render_progressive(time + half_frame, &mut field));
+        if result.is_ok() {
+            self.field_order.weave(outframe, &field, self.width);
+        }
+        self.field_frame = field;
+        let target = self.set_target(outframe);
+        result.and(target)
+    }
+
+    // Take at least `count` frame sized buffers from the scratch pool,
+    // they should be returned to self.scratch when done.
+    fn take_scratch(&mut self, count: usize) -> Vec<Vec<u32>> {
+        let mut scratch = std::mem::take(&mut self.scratch);
+        scratch.resize_with(count.max(scratch.len()), Vec::new);
+        for buffer in scratch.iter_mut() {
+            buffer.resize(self.width * self.height, 0);
+        }
+        scratch
+    }
+
+    fn render_channel_phase(&mut self, time: f64, outframe: &mut [u32]) -> anyhow::Result<()> {
+        let mut scratch = self.take_scratch(3);
+        let mut result = Ok(());
+        for (i, buffer) in scratch.iter_mut().take(3).enumerate() {
+            result = self.set_target(buffer).and_then(|_| {
+                let time = time + i as f64 * self.channel_phase;
+                self.render_sampled(time, buffer)
+            });
+            if result.is_err() {
+                break;
+            }
+        }
+        if result.is_ok() {
+            chromatic::compose(&scratch[0], &scratch[1], &scratch[2], outframe);
+        }
+        self.scratch = scratch;
+        let target = self.set_target(outframe);
+        result.and(target)
+    }
+
+    // Resolve the (fractional) frame number displayed at animation `time`,
+    // capped at max_frame outside `frames`, None if the edge policy leaves it empty
+    fn frame_number(&self, time: f64) -> anyhow::Result<Option<f32>> {
+        let frame_number = self.uncapped_frame_number(time)?;
+        Ok(if self.max_frame >= 0.0 && !self.linear {
+            frame_number.map(|frame_number| frame_number.min(self.max_frame as f32))
+        } else {
+            frame_number
+        })
+    }
+
+    fn uncapped_frame_number(&self, time: f64) -> anyhow::Result<Option<f32>> {
+        if self.linear {
+            let (start, frames, duration) = self.playback_range()?;
+            let frame_number = if duration > 0.0 {
+                start + (time as f32).clamp(0.0, duration) / duration * frames
+            } else {
+                start
+            };
+            return Ok(Some(self.frame_rounding.apply(frame_number)));
+        }
+        if let Some(frame_number) = self.goto_frame {
+            return Ok(Some(frame_number));
+        }
+        let duration = self.duration()?;
+        let total_frames = self
+            .animation
+            .get_total_frame()
+            .context("Failed to query total frames")?;
+        if duration > 0.0
+            && let Some(frame_number) = self
+                .intro_loop
+                .frame(time * (total_frames / duration) as f64)
+        {
+            return Ok(Some(rounding::step(
+                self.frame_rounding.apply(frame_number.min(total_frames)),
+                self.step_frames as f32,
+                0.0,
+                total_frames,
+            )));
+        }
+
+        let (start, frames, duration) = self.playback_range()?;
+        let time = self.dwell_time(time, start, frames, duration);
+        let frame_duration = self.frame_duration()?;
+        // Seeded by the frame index so renders are reproducible
+        let time = if self.temporal_jitter != 0.0 && frame_duration > 0.0 {
+            let index = (time / frame_duration).floor() as i64;
+            time + jitter::offset(index, self.temporal_jitter) * frame_duration
+        } else {
+            time
+        };
+        let bounce_skip = if self.bounce_skip_endpoints {
+            frame_duration as f32
+        } else {
+            0.0
+        };
+        let Some(animation_time) = self.edge.next_frame(
+            self.mode,
+            time,
+            duration,
+            self.loop_animation,
+            self.bounce_hold as f32,
+            bounce_skip,
+        ) else {
+            return Ok(None);
+        };
+
+        // Convert animation time to frame number
+        let frame_number = if duration > 0.0 {
+            self.frame_rounding
+                .apply(start + (animation_time / duration) * frames)
+        } else {
+            start
+        };
+        Ok(Some(rounding::step(
+            frame_number,
+            self.step_frames as f32,
+            start,
+            frames,
+        )))
+    }
+
+    // Render `time` into the current target `buffer`, averaging samples over the
+    // motion blur shutter if enabled
+    fn render_sampled(&mut self, time: f64, buffer: &mut [u32]) -> anyhow::Result<()> {
+        let samples = self.motion_samples();
+        if self.motion_blur <= 0.0 || samples <= 1 {
+            return self.render_time(time, buffer);
+        }
+        let frame_duration = self.frame_duration()?;
+        let mut accum = std::mem::take(&mut self.motion_accum);
+        accum.clear();
+        accum.resize(buffer.len() * 4, 0);
+        let mut result = Ok(());
+        for time in shutter::sample_times(time, self.motion_blur, frame_duration, samples) {
+            result = self.render_time(time, buffer);
+            if result.is_err() {
+                break;
+            }
+            shutter::accumulate(&mut accum, buffer);
+        }
+        if result.is_ok() {
+            shutter::average(&accum, samples, buffer);
+        }
+        self.motion_accum = accum;
+        result
+    }
+
+    // Number of motion blur renders per frame, at most MAX_MOTION_SAMPLES
+    fn motion_samples(&self) -> usize {
+        (self.motion_samples as usize).min(MAX_MOTION_SAMPLES)
+    }
+
+    fn render_time(&mut self, time: f64, buffer: &mut [u32]) -> anyhow::Result<()> {
+        let Some(frame_number) = self.frame_number(time)? else {
+            buffer.fill(0);
+            return Ok(());
+        };
+
+        let key = (
+            frame_number.to_bits(),
+            self.width,
+            self.height,
+            self.layout_generation,
+        );
+        if let Some(frame) = self.frame_cache.get(&key) {
+            buffer.copy_from_slice(frame);
+            return Ok(());
+        }
+
+        // ThorVG fails if we set the same frame, so only set it when it changes
+        if self.last_frame != Some(frame_number) {
+            match self.animation.set_frame(frame_number) {
+                Ok(()) => self.last_frame = Some(frame_number),
+                Err(err) => {
+                    self.report_error(format!("Failed to set frame {frame_number}: {err:?}"))
+                }
+            }
+        }
+        self.renderer.update()?;
+        // Tolerate transient draw failures (e.g. during a resize),
+        // update_source keeps showing the last good frame.
+        match self.draw() {
+            Ok(()) => {
+                self.consecutive_draw_errors = 0;
+                if self.last_frame == Some(frame_number) {
+                    self.frame_cache.insert(key, buffer);
+                }
+            }
+            Err(err) => {
+                self.draw_errors += 1;
+                self.consecutive_draw_errors += 1;
+                if self.consecutive_draw_errors >= MAX_CONSECUTIVE_DRAW_ERRORS {
+                    return Err(err.context(format!(
+                        "Failed to draw {} consecutive frames",
+                        self.consecutive_draw_errors
+                    )));
+                }
+                self.report_error(format!("Failed to draw, keeping last frame: {err:?}"));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw(&mut self) -> anyhow::Result<()> {
+        self.renderer.draw(true)?;
+        self.renderer.sync()?;
+        Ok(())
+    }
+
+    fn postprocess(&self, outframe: &mut [u32]) {
+        if self.tint_strength > 0.0 {
+            color::tint(
+                outframe,
+                [self.tint.r, self.tint.g, self.tint.b],
+                self.tint_strength as f32,
+            );
+        }
+        if self.saturation != 1.0 {
+            color::saturate(outframe, self.saturation as f32);
+        }
+        if let Some(matrix) = &self.color_matrix {
+            color::apply_matrix(outframe, matrix);
+        }
+        if self.exposure != 0.0 {
+            color::apply_lut(outframe, &self.exposure_lut);
+        }
+        if self.output_gamma != 1.0 {
+            color::apply_lut(outframe, &self.gamma_lut);
+        }
+        if self.alpha_threshold > 0.0 {
+            color::threshold_alpha(outframe, self.alpha_threshold as f32);
+        }
+        if self.invert_alpha {
+            color::invert_alpha(outframe);
+        }
+        if self.mirror != mirror::Mirror::None {
+            self.mirror.apply(outframe, self.width, self.height);
+        }
+        if self.mask != mask::Mask::None {
+            self.mask
+                .apply(outframe, self.width, self.height, self.mask_radius as f32);
+        }
+        if let Some(matte) = self.matte_color {
+            color::flatten(
+                outframe,
+                [matte.r, matte.g, matte.b].map(|c| (c * 255.0) as u8),
+            );
+        }
+        if self.vignette_strength > 0.0 {
+            self.vignette.apply(outframe);
+        }
+        if self.dither {
+            dither::apply(outframe, self.width);
+        }
+    }
+}
+
+struct Frames<'a> {
+    plugin: &'a mut L0ttiePlugin,
+    fps: f64,
+    index: u64,
+    done: bool,
+}
+
+impl Iterator for Frames<'_> {
+    type Item = anyhow::Result<Vec<u32>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let time = self.index as f64 / self.fps;
+        // The duration is known once the first frame has loaded the animation
+        if self.index > 0 {
+            match self.plugin.playback_range() {
+                Ok((_, _, duration)) if time < duration as f64 => {}
+                Ok(_) => {
+                    self.done = true;
+                    return None;
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+        self.index += 1;
+        let frame = self.plugin.render_to_vec(time);
+        self.done = frame.is_err();
+        Some(frame)
+    }
+}
+
+impl Drop for Frames<'_> {
+    fn drop(&mut self) {
+        self.plugin.linear = false;
+    }
+}
+
+frei0r_rs2::plugin!(L0ttiePlugin);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frei0r_rs2::{Plugin, SourcePlugin};
+
+    fn empty_plugin() -> L0ttiePlugin {
+        let data = br#"{"v":"5.7.0","fr":30,"ip":0,"op":60,"w":4,"h":2,"layers":[]}"#;
+        L0ttiePlugin::from_json_bytes(4, 2, data).unwrap()
+    }
+
+    #[test]
+    fn test_effective_duration() {
+        assert_eq!(effective_duration(2.0, 60.0), 2.0);
+        assert_eq!(effective_duration(0.0, 60.0), 2.0);
+        assert_eq!(effective_duration(0.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_render_frame_u16() {
+        let mut plugin = empty_plugin();
+        assert!(plugin.render_frame_u16(0.0, &mut [0; 4 * 2]).is_err());
+        let mut out = [1; 4 * 2 * 4];
+        plugin.render_frame_u16(0.0, &mut out).unwrap();
+        assert_eq!(out, [0; 4 * 2 * 4]);
+    }
+
+    #[test]
+    fn test_render_frame_rgba8_size() {
+        let mut plugin = L0ttiePlugin::new(4, 2);
+        assert!(plugin.render_frame_rgba8(0.0, &mut [0; 4 * 2 * 3]).is_err());
+        assert!(plugin.render_frame(0.0, &mut [0; 4]).is_err());
+    }
+
+    #[test]
+    fn test_update_source_size_mismatch() {
+        let mut plugin = empty_plugin();
+        // Both the full frame and the region paths
+        for (region, len) in [(0.0, 4 * 2 - 1), (2.0, 4 * 2 - 1), (2.0, 4 * 2 + 4)] {
+            (plugin.region_w, plugin.region_h) = (region, region);
+            plugin.last_error = CString::default();
+            let mut frame = vec![7; len];
+            plugin.update_source(0.0, &mut frame);
+            assert!(frame.iter().all(|p| *p == 7));
+            let error = plugin.last_error.to_string_lossy();
+            assert!(error.contains("Output buffer has"), "{error}");
+        }
+    }
+
+    #[test]
+    fn test_load_failure_backoff() {
+        let mut plugin = L0ttiePlugin::new(4, 2);
+        plugin.animation_path = c"/nonexistent/l0ttie.json".into();
+        let mut frame = [0; 4 * 2];
+        for _ in 0..MAX_LOAD_FAILURES {
+            assert!(plugin.render_frame(0.0, &mut frame).is_err());
+        }
+        assert_eq!(plugin.load_failures, MAX_LOAD_FAILURES);
+        // No more retries until the path changes
+        assert!(plugin.render_frame(0.0, &mut frame).is_ok());
+        plugin.animation_path = c"/nonexistent/l0ttie2.json".into();
+        assert!(plugin.render_frame(0.0, &mut frame).is_err());
+        assert_eq!(plugin.load_failures, 1);
+    }
+
+    #[test]
+    fn test_frames() {
+        let mut plugin = empty_plugin();
+        plugin.mode = mode::Mode::Reverse;
+        let frames: Vec<_> = plugin.frames(10.0).collect::<anyhow::Result<_>>().unwrap();
+        assert_eq!(frames.len(), 20);
+        assert!(frames.iter().all(|frame| frame.len() == 4 * 2));
+        assert!(!plugin.linear);
+        assert_eq!(plugin.frames(0.0).count(), 0);
+        // max_frame and the visible window don't apply either
+        plugin.max_frame = 10.0;
+        plugin.visible_start = 10.0;
+        plugin.last_good_frame.clear();
+        assert_eq!(plugin.frames(10.0).count(), 20);
+        assert_eq!(plugin.last_good_frame.len(), 4 * 2);
+        plugin.linear = true;
+        assert_eq!(plugin.frame_number(1.5).unwrap(), Some(45.0));
+        plugin.linear = false;
+        assert_eq!(plugin.frame_number(1.5).unwrap(), Some(10.0));
+    }
+
+    #[test]
+    fn test_status() {
+        let mut plugin = L0ttiePlugin::new(4, 2);
+        assert_eq!(plugin.status, status::Status::Uninitialized);
+        plugin.animation_path = c"/nonexistent/l0ttie.json".into();
+        assert!(plugin.render_frame(0.0, &mut [0; 4 * 2]).is_err());
+        assert_eq!(plugin.status, status::Status::LoadFailed);
+
+        let mut plugin = empty_plugin();
+        assert_eq!(plugin.status, status::Status::Loading);
+        plugin.render_frame(0.0, &mut [0; 4 * 2]).unwrap();
+        assert_eq!(plugin.status, status::Status::Rendering);
+        assert!(plugin.render_frame(0.0, &mut [0; 4]).is_err());
+        assert_eq!(plugin.status, status::Status::RenderFailed);
+
+        plugin.reset = true;
+        plugin.animation_path = c"/nonexistent/l0ttie.json".into();
+        assert!(plugin.render_frame(0.0, &mut [0; 4 * 2]).is_err());
+        assert_eq!(plugin.status, status::Status::LoadFailed);
+    }
+
+    #[test]
+    fn test_background_rgba_rebuilds() {
+        let mut plugin = empty_plugin();
+        plugin
+            .apply_config(r##"{"background_rgba":"#ff000080"}"##)
+            .unwrap();
+        assert_eq!(plugin.background_rgba, Some([255, 0, 0, 128]));
+        assert!(plugin.rebuild_background);
+        plugin.render_frame(0.0, &mut [0; 4 * 2]).unwrap();
+        assert!(!plugin.rebuild_background);
+        // The same color parsed from different text is not a change
+        plugin
+            .apply_config(r##"{"background_rgba":"#FF000080"}"##)
+            .unwrap();
+        assert!(!plugin.rebuild_background);
+    }
+
+    #[test]
+    fn test_max_time_scale() {
+        let mut plugin = L0ttiePlugin::new(4, 2);
+        plugin.time_scale = 1e9;
+        // Clamped by default
+        assert_eq!(plugin.time_scale(), 1000.0);
+        plugin.time_scale = -5000.0;
+        assert_eq!(plugin.time_scale(), -1000.0);
+        // 0 is no limit
+        plugin.max_time_scale = 0.0;
+        assert_eq!(plugin.time_scale(), -5000.0);
+    }
+
+    #[test]
+    fn test_motion_samples() {
+        let mut plugin = empty_plugin();
+        assert_eq!(plugin.motion_samples(), 4);
+        plugin.motion_samples = 1e12;
+        assert_eq!(plugin.motion_samples(), MAX_MOTION_SAMPLES);
+        plugin.motion_blur = 0.5;
+        let mut frame = [0; 4 * 2];
+        plugin.render_frame(0.0, &mut frame).unwrap();
+    }
+
+    #[test]
+    fn test_frame_lock() {
+        let mut plugin = empty_plugin();
+        plugin.frame_lock = true;
+        plugin.time_scale = 10.0;
+        let mut frame = [0; 4 * 2];
+        for time in [0.0, 5.0, 100.0] {
+            plugin.render_frame(time, &mut frame).unwrap();
+        }
+        assert!((plugin.playhead - 3.0 / 30.0).abs() < 1e-6);
+        // Reused renders don't advance
+        plugin.max_fps = 0.001;
+        plugin.max_frame = 0.0;
+        for time in [0.0, 5.0, 100.0] {
+            plugin.render_frame(time, &mut frame).unwrap();
+        }
+        assert!((plugin.playhead - 4.0 / 30.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_realtime() {
+        let mut plugin = empty_plugin();
+        plugin.realtime = true;
+        plugin.time_scale = 2.0;
+        let mut frame = [0; 4 * 2];
+        plugin.render_frame(100.0, &mut frame).unwrap();
+        assert!(plugin.current_time < 1.0);
+        plugin.realtime_start = Some(Instant::now() - Duration::from_secs(1));
+        plugin.render_frame(0.0, &mut frame).unwrap();
+        assert!((plugin.current_time - 2.0).abs() < 0.5);
+        plugin.unload();
+        assert!(plugin.realtime_start.is_none());
+    }
+
+    #[test]
+    fn test_goto_marker() {
+        let data = br#"{"v":"5.7.0","fr":30,"ip":0,"op":60,"w":4,"h":2,"layers":[],
+            "markers":[{"cm":"a","tm":0,"dr":10},{"cm":"b","tm":20,"dr":10}]}"#;
+        let mut plugin = L0ttiePlugin::from_json_bytes(4, 2, data).unwrap();
+        plugin.goto_marker = 1.0;
+        plugin.resolve_goto_marker();
+        assert_eq!(plugin.frame_number(0.5).unwrap(), Some(20.0));
+        assert!(plugin.last_error.is_empty());
+
+        plugin.goto_marker = 5.0;
+        plugin.resolve_goto_marker();
+        assert_eq!(plugin.frame_number(0.0).unwrap(), Some(20.0));
+        assert!(!plugin.last_error.is_empty());
+
+        plugin.goto_marker = -1.0;
+        plugin.resolve_goto_marker();
+        assert_eq!(plugin.goto_frame, None);
+    }
+
+    #[test]
+    fn test_byte_order() {
+        // Full frame opaque red solid layer
+        let data = br##"{"v":"5.7.0","fr":30,"ip":0,"op":60,"w":4,"h":2,"layers":[
+            {"ty":1,"ind":1,"ip":0,"op":60,"st":0,"sw":4,"sh":2,"sc":"#ff0000",
+             "ks":{"o":{"a":0,"k":100},"r":{"a":0,"k":0},"p":{"a":0,"k":[0,0,0]},
+                   "a":{"a":0,"k":[0,0,0]},"s":{"a":0,"k":[100,100,100]}}}]}"##;
+        let mut plugin = L0ttiePlugin::from_json_bytes(4, 2, data).unwrap();
+        let mut frame = [0; 4 * 2];
+        plugin.render_frame(0.0, &mut frame).unwrap();
+        assert_eq!(frame, [0xff0000ff; 4 * 2]);
+        // frei0r RGBA8888 is R, G, B, A in memory, true on little-endian hosts
+        assert_eq!(frame[0].to_ne_bytes(), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let data = b"{\"v\":\"5.7.0\",\"nm\":\"bad \xff name\",\"fr\":30,\"ip\":0,\"op\":60,\"w\":4,\"h\":2,\"layers\":[]}";
+        let plugin = L0ttiePlugin::from_json_bytes(4, 2, data).unwrap();
+        assert!(plugin.loaded);
+        assert!(plugin.last_error.to_bytes().starts_with(b"Invalid UTF-8"));
+        assert_eq!(plugin.anim_name.to_str().unwrap(), "bad \u{fffd} name");
+    }
+
+    #[test]
+    fn test_set_time() {
+        let mut plugin = empty_plugin();
+        plugin.mode = mode::Mode::Reverse;
+        plugin.time_scale = 2.0;
+        plugin.set_time(0.25);
+        assert_eq!(plugin.current_frame(), 45.0);
+        plugin.render_frame(0.5, &mut [0; 4 * 2]).unwrap();
+        assert_eq!(plugin.current_frame(), 30.0);
+    }
+
+    #[test]
+    fn test_render_frame_split() {
+        let mut plugin = empty_plugin();
+        let (mut rgb, mut alpha) = ([0; 4 * 2], [0; 4 * 2]);
+        assert!(
+            plugin
+                .render_frame_split(0.0, &mut rgb, &mut [0; 4])
+                .is_err()
+        );
+        plugin
+            .render_frame_split(0.0, &mut rgb, &mut alpha)
+            .unwrap();
+        assert_eq!(rgb, [pixel::pack([0, 0, 0, 255]); 4 * 2]);
+        assert_eq!(alpha, [0; 4 * 2]);
+    }
+
+    #[test]
+    fn test_visible_window() {
+        let mut plugin = empty_plugin();
+        (plugin.visible_start, plugin.visible_end) = (1.0, 2.0);
+        let mut frame = [7; 4 * 2];
+        plugin.render_frame(0.5, &mut frame).unwrap();
+        assert_eq!(frame, [0; 4 * 2]);
+        assert_eq!(plugin.status, status::Status::Loading);
+        plugin.render_frame(1.5, &mut frame).unwrap();
+        assert_eq!(plugin.status, status::Status::Rendering);
+        frame.fill(7);
+        plugin.render_frame(2.5, &mut frame).unwrap();
+        assert_eq!(frame, [0; 4 * 2]);
+        assert!(!plugin.visible(100.0));
+        plugin.visible_end = 0.0;
+        assert!(plugin.visible(100.0));
+
+        // Only the region is cleared, a mis-sized buffer is an error
+        plugin.visible_start = 10.0;
+        (plugin.region_w, plugin.region_h) = (2.0, 1.0);
+        frame.fill(7);
+        plugin.render_frame(0.0, &mut frame).unwrap();
+        assert_eq!(frame, [0, 0, 7, 7, 7, 7, 7, 7]);
+        assert!(plugin.render_frame(0.0, &mut [7; 3]).is_err());
+    }
+
+    #[test]
+    fn test_region() {
+        let data = br#"{"v":"5.7.0","fr":30,"ip":0,"op":60,"w":4,"h":2,"layers":[]}"#;
+        let mut plugin = L0ttiePlugin::from_json_bytes(8, 4, data).unwrap();
+        (plugin.region_x, plugin.region_y) = (2.0, 1.0);
+        (plugin.region_w, plugin.region_h) = (4.0, 10.0);
+        let mut frame = [7; 8 * 4];
+        plugin.render_frame(0.0, &mut frame).unwrap();
+        for (i, pixel) in frame.iter().enumerate() {
+            let (x, y) = (i % 8, i / 8);
+            let inside = (2..6).contains(&x) && y >= 1;
+            assert_eq!(*pixel == 7, !inside, "pixel {x},{y}");
+        }
+        assert_eq!((plugin.width, plugin.height), (8, 4));
+    }
+
+    #[test]
+    fn test_animation_json() {
+        let mut plugin = L0ttiePlugin::new(4, 2);
+        plugin.animation_path = c"/nonexistent/l0ttie.json".into();
+        plugin.animation_json =
+            cr#"{"v":"5.7.0","fr":30,"ip":0,"op":60,"w":4,"h":2,"layers":[]}"#.into();
+        plugin.render_frame(0.0, &mut [0; 4 * 2]).unwrap();
+        assert!(plugin.loaded);
+    }
+
+    #[test]
+    fn test_step_frames() {
+        let mut plugin = empty_plugin();
+        plugin.step_frames = 5.0;
+        for i in 0..120 {
+            let frame = plugin.frame_number(i as f64 / 60.0).unwrap().unwrap();
+            assert_eq!(frame % 5.0, 0.0, "frame {frame}");
+        }
+        // 7 doesn't divide the 60 frames, the end holds the last multiple
+        plugin.step_frames = 7.0;
+        for i in 0..120 {
+            let frame = plugin.frame_number(i as f64 / 60.0).unwrap().unwrap();
+            assert_eq!(frame % 7.0, 0.0, "frame {frame}");
+        }
+        assert_eq!(plugin.frame_number(1.999).unwrap(), Some(56.0));
+    }
+
+    #[test]
+    fn test_markers_span() {
+        let data = br#"{"v":"5.7.0","fr":30,"ip":0,"op":60,"w":4,"h":2,"layers":[],
+            "markers":[{"cm":"a","tm":10,"dr":10},{"cm":"b","tm":30,"dr":15}]}"#;
+        let mut plugin = L0ttiePlugin::from_json_bytes(4, 2, data).unwrap();
+        plugin.markers_span = true;
+        plugin.resolve_marker();
+        assert_eq!(plugin.playback_range().unwrap(), (10.0, 35.0, 35.0 / 30.0));
+        plugin.marker = c"a".into();
+        plugin.resolve_marker();
+        assert_eq!(plugin.marker_range, Some((10.0, 10.0)));
+    }
+
+    #[test]
+    fn test_temporal_jitter() {
+        let mut plugin = empty_plugin();
+        // The default of 0 leaves frames unjittered
+        assert_eq!(plugin.temporal_jitter, 0.0);
+        let frames = [0.0, 0.5, 0.55, 1.2].map(|time| plugin.frame_number(time).unwrap());
+        assert_eq!(frames, [Some(0.0), Some(15.0), Some(16.5), Some(36.0)]);
+        plugin.temporal_jitter = 0.5;
+        let jittered = plugin.frame_number(0.55).unwrap().unwrap();
+        assert!((jittered - 16.5).abs() <= 0.25, "{jittered}");
+        assert_eq!(plugin.frame_number(0.55).unwrap(), Some(jittered));
+    }
+
+    #[test]
+    fn test_theme_path() {
+        let mut plugin = empty_plugin();
+        plugin.apply_theme().unwrap();
+
+        let path = std::env::temp_dir().join("l0ttie-missing-theme.json");
+        plugin.theme_path = CString::new(path.to_str().unwrap()).unwrap();
+        assert!(plugin.apply_theme().is_err());
+    }
+
+    #[test]
+    fn test_onion_skin() {
+        let mut plugin = empty_plugin();
+        plugin.onion_skin = 3.0;
+        let mut frame = [0; 4 * 2];
+        plugin.render_frame(0.5, &mut frame).unwrap();
+        assert_eq!(plugin.onion_frame.len(), 4 * 2);
+        assert_eq!(plugin.consecutive_draw_errors, 0);
+    }
+
+    #[test]
+    fn test_time_map() {
+        let mut plugin = empty_plugin();
+        plugin.time_scale = 3.0;
+        plugin.time_map = Some(timemap::TimeMap::parse("0:0,10:1").unwrap());
+        plugin.set_time(5.0);
+        assert_eq!(plugin.current_frame(), 15.0);
+        plugin.set_time(20.0);
+        assert_eq!(plugin.current_frame(), 30.0);
+    }
+
+    #[test]
+    fn test_marker_dwell() {
+        let data = br#"{"v":"5.7.0","fr":30,"ip":0,"op":60,"w":4,"h":2,"layers":[],
+            "markers":[{"cm":"a","tm":30,"dr":10}]}"#;
+        let mut plugin = L0ttiePlugin::from_json_bytes(4, 2, data).unwrap();
+        plugin.marker_dwell = 1.0;
+        plugin.loop_animation = true;
+        assert_eq!(plugin.frame_number(0.5).unwrap(), Some(15.0));
+        assert_eq!(plugin.frame_number(1.5).unwrap(), Some(30.0));
+        assert_eq!(plugin.frame_number(2.5).unwrap(), Some(45.0));
+        // The pause repeats every 3 second pass
+        assert_eq!(plugin.frame_number(4.5).unwrap(), Some(30.0));
+        plugin.mode = mode::Mode::Reverse;
+        assert_eq!(plugin.frame_number(0.5).unwrap(), Some(45.0));
+
+        // once is not finished while paused
+        plugin.mode = mode::Mode::Once;
+        let mut frame = [0; 4 * 2];
+        plugin.render_frame(2.5, &mut frame).unwrap();
+        assert!(!plugin.finished);
+        plugin.render_frame(3.0, &mut frame).unwrap();
+        assert!(plugin.finished);
+    }
+
+    #[test]
+    fn test_crop_decor() {
+        let mut plugin = L0ttiePlugin::new(4, 2);
+        plugin.animation_json =
+            cr#"{"v":"5.7.0","fr":30,"ip":0,"op":60,"w":4,"h":2,"layers":[]}"#.into();
+        plugin.background_color = Some(frei0r_rs2::Color {
+            r: 1.0,
+            g: 0.0,
+            b: 0.0,
+        });
+        plugin.crop_w = 2.0;
+        plugin.crop_h = 2.0;
+        let mut frame = [0; 4 * 2];
+        plugin.render_frame(0.0, &mut frame).unwrap();
+        // The background is drawn separately so the crop does not clip it
+        assert!(plugin.decor.is_some());
+        assert!(plugin.crop_clip.is_some());
+
+        plugin.crop_w = 0.0;
+        plugin.recompute_layout = true;
+        plugin.render_frame(0.0, &mut frame).unwrap();
+        assert!(plugin.decor.is_none());
+    }
+
+    #[test]
+    fn test_config_round_trip() {
+        let mut source = empty_plugin();
+        let mut target = empty_plugin();
+        target.apply_config(&source.config_string()).unwrap();
+        // Unset optional colors stay unset
+        assert!(target.background_color.is_none());
+        assert!(target.matte_color.is_none());
+        assert!(target.bar_color.is_none());
+
+        source.time_scale = 2.0;
+        source.loop_animation = true;
+        source.mode = mode::Mode::Reverse;
+        source.matte_color = Some(frei0r_rs2::Color {
+            r: 1.0,
+            g: 0.0,
+            b: 0.5,
+        });
+        let config = source.config_string();
+        assert!(!config.contains("last_error"));
+        target.apply_config(&config).unwrap();
+        assert_eq!(target.config_string(), config);
+        assert_eq!(target.time_scale, 2.0);
+        assert!(target.matte_color.is_some());
+        assert_eq!(target.frame_number(0.25).unwrap(), Some(52.5));
+        assert!(target.apply_config(r#"{"bogus":1}"#).is_err());
+    }
+
+    #[test]
+    fn test_config_param() {
+        let mut plugin = empty_plugin();
+        let param = L0ttiePlugin::PARAMS
+            .iter()
+            .find(|param| param.name == c"config")
+            .unwrap();
+        let frei0r_rs2::ParamKind::String { get, .. } = param.kind else {
+            panic!("config is not a string param");
+        };
+        assert_eq!(get(&plugin).to_str().unwrap(), plugin.config_string());
+        // Rebuilt after the next frame
+        plugin.time_scale = 2.0;
+        plugin.update_source(0.0, &mut [0; 4 * 2]);
+        assert_eq!(get(&plugin).to_str().unwrap(), plugin.config_string());
+    }
+
+    #[cfg(feature = "embedded_animation")]
+    #[test]
+    fn test_embedded_animation() {
+        let mut plugin = L0ttiePlugin::new(4, 2);
+        plugin.render_frame(0.0, &mut [0; 4 * 2]).unwrap();
+        assert!(plugin.loaded);
+    }
+
+    #[test]
+    fn test_max_frame() {
+        let mut plugin = empty_plugin();
+        plugin.max_frame = 10.0;
+        assert_eq!(plugin.frame_number(0.1).unwrap(), Some(3.0));
+        assert_eq!(plugin.frame_number(1.0).unwrap(), Some(10.0));
+        plugin.max_frame = -1.0;
+        assert_eq!(plugin.frame_number(1.0).unwrap(), Some(30.0));
+    }
+
+    #[test]
+    fn test_from_bytes_invalid() {
+        assert!(L0ttiePlugin::from_json_bytes(4, 2, &[0xff, 0xfe]).is_err());
+        assert!(L0ttiePlugin::from_dotlottie_bytes(4, 2, b"{}").is_err());
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde_json::{Map, Value};
+
+/// One line JSON log record for log aggregators.
+pub(crate) fn json_record(level: &str, message: &str, path: &str, frame: Option<f32>) -> String {
+    let mut record = Map::new();
+    record.insert("level".to_owned(), Value::from(level));
+    record.insert("message".to_owned(), Value::from(message));
+    record.insert("path".to_owned(), Value::from(path));
+    record.insert(
+        "frame".to_owned(),
+        frame.map_or(Value::Null, |frame| Value::from(frame as f64)),
+    );
+    Value::Object(record).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_record() {
+        let record = json_record("error", "Failed \"line\"\nnext", "/tmp/a.json", Some(12.0));
+        assert!(!record.contains('\n'));
+        let json: Value = serde_json::from_str(&record).unwrap();
+        assert_eq!(json["level"].as_str(), Some("error"));
+        assert_eq!(json["message"].as_str(), Some("Failed \"line\"\nnext"));
+        assert_eq!(json["path"].as_str(), Some("/tmp/a.json"));
+        assert_eq!(json["frame"].as_f64(), Some(12.0));
+
+        let json: Value = serde_json::from_str(&json_record("warn", "", "", None)).unwrap();
+        assert!(json["frame"].is_null());
+    }
+}
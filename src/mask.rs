@@ -0,0 +1,158 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::ffi::CStr;
+
+use crate::pixel;
+
+/// Shape the output frame is clipped to.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Mask {
+    None,
+    Circle,
+    Rounded,
+}
+
+impl Mask {
+    // Signed distance from (x, y) to the mask edge, negative inside.
+    // `radius` is the circle radius or rounded corner radius.
+    fn distance(&self, x: f32, y: f32, half: [f32; 2], radius: f32) -> f32 {
+        let (dx, dy) = ((x - half[0]).abs(), (y - half[1]).abs());
+        match self {
+            Mask::None => f32::NEG_INFINITY,
+            Mask::Circle => dx.hypot(dy) - radius,
+            Mask::Rounded => {
+                let (qx, qy) = (dx - (half[0] - radius), dy - (half[1] - radius));
+                qx.max(0.0).hypot(qy.max(0.0)) + qx.max(qy).min(0.0) - radius
+            }
+        }
+    }
+
+    /// Clip `frame` to the mask with antialiased edges. `radius` is the circle radius,
+    /// or the corner radius for rounded; radius <= 0 selects the largest that fits.
+    pub(crate) fn apply(&self, frame: &mut [u32], width: usize, height: usize, radius: f32) {
+        if *self == Mask::None || width == 0 {
+            return;
+        }
+        let half = [width as f32 / 2.0, height as f32 / 2.0];
+        let max_radius = half[0].min(half[1]);
+        let radius = if radius > 0.0 {
+            radius.min(max_radius)
+        } else {
+            max_radius
+        };
+        for (y, row) in frame.chunks_exact_mut(width).enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let distance = self.distance(x as f32 + 0.5, y as f32 + 0.5, half, radius);
+                let coverage = (0.5 - distance).clamp(0.0, 1.0);
+                if coverage < 1.0 {
+                    let rgba = pixel::unpack(*pixel);
+                    *pixel = pixel::pack(rgba.map(|c| (c as f32 * coverage).round() as u8));
+                }
+            }
+        }
+    }
+}
+
+/// Clear `frame` outside the `[x, y, width, height]` rect, antialiasing partially covered pixels.
+pub(crate) fn clip_rect(frame: &mut [u32], width: usize, height: usize, rect: [f32; 4]) {
+    if width == 0 {
+        return;
+    }
+    // Fraction of the pixel span [i, i + 1) inside [start, end)
+    let coverage = |i: usize, start: f32, end: f32| {
+        ((i as f32 + 1.0).min(end) - (i as f32).max(start)).clamp(0.0, 1.0)
+    };
+    let [x, y, w, h] = rect;
+    for (row, line) in frame.chunks_exact_mut(width).enumerate().take(height) {
+        let row_coverage = coverage(row, y, y + h);
+        for (column, pixel) in line.iter_mut().enumerate() {
+            let coverage = row_coverage * coverage(column, x, x + w);
+            if coverage < 1.0 {
+                let rgba = pixel::unpack(*pixel);
+                *pixel = pixel::pack(rgba.map(|c| (c as f32 * coverage).round() as u8));
+            }
+        }
+    }
+}
+
+pub(crate) const MASK_NONE: &CStr = c"none";
+pub(crate) const MASK_CIRCLE: &CStr = c"circle";
+pub(crate) const MASK_ROUNDED: &CStr = c"rounded";
+
+impl From<&CStr> for Mask {
+    fn from(value: &CStr) -> Self {
+        if value == MASK_CIRCLE {
+            Mask::Circle
+        } else if value == MASK_ROUNDED {
+            Mask::Rounded
+        } else {
+            Mask::None
+        }
+    }
+}
+
+impl From<Mask> for &'static CStr {
+    fn from(mask: Mask) -> Self {
+        match mask {
+            Mask::None => MASK_NONE,
+            Mask::Circle => MASK_CIRCLE,
+            Mask::Rounded => MASK_ROUNDED,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPAQUE: u32 = 0xffffffff;
+
+    #[test]
+    fn test_circle() {
+        let mut frame = [OPAQUE; 16 * 16];
+        Mask::Circle.apply(&mut frame, 16, 16, 0.0);
+        // Corners are outside, center inside
+        assert_eq!(frame[0], 0);
+        assert_eq!(frame[16 * 16 - 1], 0);
+        assert_eq!(frame[8 * 16 + 8], OPAQUE);
+        assert_eq!(frame[8 * 16 + 1], OPAQUE);
+        assert!(frame[8 * 16] != OPAQUE && frame[8 * 16] != 0);
+    }
+
+    #[test]
+    fn test_rounded() {
+        let mut frame = [OPAQUE; 16 * 8];
+        Mask::Rounded.apply(&mut frame, 16, 8, 3.0);
+        assert_eq!(frame[0], 0);
+        assert_eq!(frame[15], 0);
+        // Edges away from the corners are unaffected
+        assert_eq!(frame[8], OPAQUE);
+        assert_eq!(frame[4 * 16], OPAQUE);
+    }
+
+    #[test]
+    fn test_none() {
+        let mut frame = [OPAQUE; 4];
+        Mask::None.apply(&mut frame, 2, 2, 0.0);
+        assert_eq!(frame, [OPAQUE; 4]);
+    }
+
+    #[test]
+    fn test_clip_rect() {
+        let mut frame = [OPAQUE; 4 * 3];
+        clip_rect(&mut frame, 4, 3, [1.0, 1.0, 2.5, 5.0]);
+        assert_eq!(frame[0..4], [0; 4]);
+        assert_eq!(frame[4..7], [0, OPAQUE, OPAQUE]);
+        assert_eq!(pixel::unpack(frame[7]), [128; 4]);
+        assert_eq!(frame[8], 0);
+    }
+
+    #[test]
+    fn test_mask_from_cstr() {
+        assert_eq!(Mask::from(MASK_NONE), Mask::None);
+        assert_eq!(Mask::from(MASK_CIRCLE), Mask::Circle);
+        assert_eq!(Mask::from(MASK_ROUNDED), Mask::Rounded);
+        assert_eq!(Mask::from(c"unknown"), Mask::None);
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde_json::Value;
+
+/// A named frame range.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Marker {
+    pub name: String,
+    pub start: f32,
+    pub frames: f32,
+}
+
+/// Start frame and frame count from the earliest marker start to the latest
+/// marker end, None without markers.
+pub(crate) fn span(markers: &[Marker]) -> Option<(f32, f32)> {
+    let start = markers.iter().map(|marker| marker.start).reduce(f32::min)?;
+    let end = markers
+        .iter()
+        .map(|marker| marker.start + marker.frames)
+        .reduce(f32::max)?;
+    Some((start, end - start))
+}
+
+/// Animation properties ThorVG does not expose, read from the Lottie JSON.
+#[derive(Debug, Default)]
+pub(crate) struct Metadata {
+    pub name: Option<String>,
+    pub markers: Vec<Marker>,
+    // Names of the visible top level layers, matte sources excluded
+    pub layers: Vec<String>,
+}
+
+// Markers are {"cm": name, "tm": start frame, "dr": duration in frames}
+fn parse_marker(marker: &Value) -> Option<Marker> {
+    Some(Marker {
+        name: marker.get("cm")?.as_str()?.to_owned(),
+        start: marker.get("tm")?.as_f64()? as f32,
+        frames: marker.get("dr").and_then(Value::as_f64).unwrap_or(0.0) as f32,
+    })
+}
+
+fn parse_layer(layer: &Value) -> Option<String> {
+    let flag = |key| layer.get(key).and_then(Value::as_f64) == Some(1.0);
+    let hidden = layer.get("hd").and_then(Value::as_bool) == Some(true);
+    if hidden || flag("td") {
+        return None;
+    }
+    layer.get("nm")?.as_str().map(str::to_owned)
+}
+
+impl Metadata {
+    pub(crate) fn parse(data: &str) -> Self {
+        let Ok(json) = serde_json::from_str::<Value>(data) else {
+            return Self::default();
+        };
+        Metadata {
+            name: json.get("nm").and_then(Value::as_str).map(str::to_owned),
+            markers: json
+                .get("markers")
+                .and_then(Value::as_array)
+                .map(|markers| markers.iter().filter_map(parse_marker).collect())
+                .unwrap_or_default(),
+            layers: json
+                .get("layers")
+                .and_then(Value::as_array)
+                .map(|layers| layers.iter().filter_map(parse_layer).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name() {
+        let metadata = Metadata::parse(r#"{"v":"5.7.4","nm":"Emoji","w":512,"h":512}"#);
+        assert_eq!(metadata.name.as_deref(), Some("Emoji"));
+    }
+
+    #[test]
+    fn test_missing() {
+        assert_eq!(Metadata::parse(r#"{"w":512}"#).name, None);
+        assert_eq!(Metadata::parse("not json").name, None);
+    }
+
+    #[test]
+    fn test_markers() {
+        let metadata = Metadata::parse(
+            r#"{"markers":[{"cm":"intro","tm":0,"dr":30},{"cm":"idle","tm":30.5,"dr":60},{"tm":5}]}"#,
+        );
+        assert_eq!(
+            metadata.markers,
+            [
+                Marker {
+                    name: "intro".to_owned(),
+                    start: 0.0,
+                    frames: 30.0
+                },
+                Marker {
+                    name: "idle".to_owned(),
+                    start: 30.5,
+                    frames: 60.0
+                },
+            ]
+        );
+        assert!(Metadata::parse(r#"{"w":512}"#).markers.is_empty());
+    }
+
+    #[test]
+    fn test_span() {
+        let marker = |start, frames| Marker {
+            name: String::new(),
+            start,
+            frames,
+        };
+        assert_eq!(span(&[]), None);
+        assert_eq!(
+            span(&[marker(30.0, 10.0), marker(5.0, 10.0), marker(20.0, 5.0)]),
+            Some((5.0, 35.0))
+        );
+    }
+
+    #[test]
+    fn test_layers() {
+        let metadata = Metadata::parse(
+            r#"{"layers":[{"nm":"card"},{"nm":"matte","td":1},{"nm":"hidden","hd":true},{"ty":4},{"nm":"logo","tt":1}]}"#,
+        );
+        assert_eq!(metadata.layers, ["card", "logo"]);
+    }
+}
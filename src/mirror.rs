@@ -0,0 +1,145 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::ffi::CStr;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Mirror {
+    None,
+    Horizontal,
+    Vertical,
+    Quad,
+}
+
+impl Mirror {
+    /// Reflect the left half onto the right (horizontal), the top half onto the bottom (vertical),
+    /// or the top-left quadrant into all four (quad).
+    pub(crate) fn apply(&self, frame: &mut [u32], width: usize, height: usize) {
+        if width == 0 {
+            return;
+        }
+        if matches!(self, Mirror::Horizontal | Mirror::Quad) {
+            for row in frame.chunks_exact_mut(width).take(height) {
+                let (left, right) = row.split_at_mut(width / 2);
+                for (dst, src) in right.iter_mut().rev().zip(left.iter()) {
+                    *dst = *src;
+                }
+            }
+        }
+        if matches!(self, Mirror::Vertical | Mirror::Quad) {
+            for y in 0..height / 2 {
+                let (top, bottom) = frame.split_at_mut((height - 1 - y) * width);
+                bottom[..width].copy_from_slice(&top[y * width..(y + 1) * width]);
+            }
+        }
+    }
+}
+
+/// Reverse the row order of `frame`, turning it upside down.
+pub(crate) fn flip_rows(frame: &mut [u32], width: usize) {
+    if width == 0 {
+        return;
+    }
+    let rows = frame.len() / width;
+    for y in 0..rows / 2 {
+        let (top, bottom) = frame.split_at_mut((rows - 1 - y) * width);
+        top[y * width..(y + 1) * width].swap_with_slice(&mut bottom[..width]);
+    }
+}
+
+pub(crate) const MIRROR_NONE: &CStr = c"none";
+pub(crate) const MIRROR_HORIZONTAL: &CStr = c"horizontal";
+pub(crate) const MIRROR_VERTICAL: &CStr = c"vertical";
+pub(crate) const MIRROR_QUAD: &CStr = c"quad";
+
+impl From<&CStr> for Mirror {
+    fn from(value: &CStr) -> Self {
+        if value == MIRROR_HORIZONTAL {
+            Mirror::Horizontal
+        } else if value == MIRROR_VERTICAL {
+            Mirror::Vertical
+        } else if value == MIRROR_QUAD {
+            Mirror::Quad
+        } else {
+            Mirror::None
+        }
+    }
+}
+
+impl From<Mirror> for &'static CStr {
+    fn from(mirror: Mirror) -> Self {
+        match mirror {
+            Mirror::None => MIRROR_NONE,
+            Mirror::Horizontal => MIRROR_HORIZONTAL,
+            Mirror::Vertical => MIRROR_VERTICAL,
+            Mirror::Quad => MIRROR_QUAD,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustfmt::skip]
+    const FRAME: [u32; 9] = [
+        1, 2, 3,
+        4, 5, 6,
+        7, 8, 9,
+    ];
+
+    fn mirrored(mirror: Mirror) -> [u32; 9] {
+        let mut frame = FRAME;
+        mirror.apply(&mut frame, 3, 3);
+        frame
+    }
+
+    #[test]
+    fn test_none() {
+        assert_eq!(mirrored(Mirror::None), FRAME);
+    }
+
+    #[test]
+    fn test_horizontal() {
+        assert_eq!(mirrored(Mirror::Horizontal), [1, 2, 1, 4, 5, 4, 7, 8, 7]);
+    }
+
+    #[test]
+    fn test_vertical() {
+        assert_eq!(mirrored(Mirror::Vertical), [1, 2, 3, 4, 5, 6, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_quad() {
+        assert_eq!(mirrored(Mirror::Quad), [1, 2, 1, 4, 5, 4, 1, 2, 1]);
+    }
+
+    #[test]
+    fn test_even_width() {
+        let mut frame = [1, 2, 3, 4];
+        Mirror::Horizontal.apply(&mut frame, 4, 1);
+        assert_eq!(frame, [1, 2, 2, 1]);
+    }
+
+    #[test]
+    fn test_mirror_from_cstr() {
+        assert_eq!(Mirror::from(MIRROR_NONE), Mirror::None);
+        assert_eq!(Mirror::from(MIRROR_HORIZONTAL), Mirror::Horizontal);
+        assert_eq!(Mirror::from(MIRROR_VERTICAL), Mirror::Vertical);
+        assert_eq!(Mirror::from(MIRROR_QUAD), Mirror::Quad);
+        assert_eq!(Mirror::from(c"unknown"), Mirror::None);
+    }
+
+    #[test]
+    fn test_mirror_to_cstr() {
+        assert_eq!(<&CStr>::from(Mirror::None), MIRROR_NONE);
+        assert_eq!(<&CStr>::from(Mirror::Quad), MIRROR_QUAD);
+    }
+
+    #[test]
+    fn test_flip_rows() {
+        let mut frame = FRAME;
+        flip_rows(&mut frame, 3);
+        assert_eq!(frame, [7, 8, 9, 4, 5, 6, 1, 2, 3]);
+    }
+}
@@ -3,21 +3,64 @@
 
 use std::ffi::CStr;
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub(crate) enum Mode {
     Forward,
     Reverse,
     Bounce,
     ReverseBounce,
+    // Forward once, then hold the final frame
+    Once,
+}
+
+// Position within a bounce cycle, holding for `hold` at 0 and at `duration`.
+// The hold at `duration` and the return leg are each shortened by `skip`
+// so the endpoint frames are not shown twice in a row.
+fn bounce_time(cycle_time: f32, duration: f32, hold: f32, skip: f32) -> f32 {
+    if cycle_time < hold {
+        0.0
+    } else if cycle_time < hold + duration {
+        cycle_time - hold
+    } else if cycle_time < 2.0 * hold + duration - skip {
+        duration
+    } else {
+        (2.0 * (hold + duration - skip) - cycle_time).max(0.0)
+    }
 }
 
 impl Mode {
-    pub(crate) fn next_frame(&self, time: f64, duration: f32, loop_animation: bool) -> f32 {
-        let time = time as f32;
+    // Length of one playback cycle, bounce modes play forward and back.
+    // `bounce_skip` is the time of one frame when skipping bounce endpoints, else 0.
+    pub(crate) fn cycle_duration(&self, duration: f32, bounce_hold: f32, bounce_skip: f32) -> f32 {
+        match self {
+            Mode::Forward | Mode::Reverse | Mode::Once => duration,
+            Mode::Bounce | Mode::ReverseBounce => {
+                2.0 * (duration + bounce_hold.max(0.0) - bounce_skip.clamp(0.0, duration / 2.0))
+            }
+        }
+    }
 
+    pub(crate) fn next_frame(
+        &self,
+        time: f64,
+        duration: f32,
+        loop_animation: bool,
+        bounce_hold: f32,
+        bounce_skip: f32,
+    ) -> f32 {
         if duration <= 0.0 {
             return 0.0;
         }
+        let bounce_hold = bounce_hold.max(0.0);
+        let bounce_skip = bounce_skip.clamp(0.0, duration / 2.0);
+
+        // Reduce looping time to a single cycle in f64 before converting,
+        // f32 loses precision for the large times of long sessions
+        let time = if loop_animation && !matches!(self, Mode::Once) {
+            (time % self.cycle_duration(duration, bounce_hold, bounce_skip) as f64) as f32
+        } else {
+            time as f32
+        };
 
         match self {
             Mode::Forward => {
@@ -34,48 +77,42 @@ impl Mode {
                     (duration - time).max(0.0)
                 }
             }
-            Mode::Bounce => {
-                let cycle_duration = 2.0 * duration;
-                if loop_animation {
-                    let cycle_time = time % cycle_duration;
-                    if cycle_time <= duration {
-                        cycle_time
-                    } else {
-                        cycle_duration - cycle_time
-                    }
-                } else if time <= duration {
-                    time
-                } else if time <= cycle_duration {
-                    cycle_duration - time
+            Mode::Once => {
+                if time >= duration {
+                    duration
                 } else {
-                    0.0
+                    time.max(0.0)
                 }
             }
-            Mode::ReverseBounce => {
-                let cycle_duration = 2.0 * duration;
-                if loop_animation {
-                    let cycle_time = time % cycle_duration;
-                    if cycle_time <= duration {
-                        duration - cycle_time
-                    } else {
-                        cycle_time - duration
-                    }
-                } else if time <= duration {
-                    duration - time
-                } else if time <= cycle_duration {
-                    time - duration
+            Mode::Bounce | Mode::ReverseBounce => {
+                let cycle_duration = self.cycle_duration(duration, bounce_hold, bounce_skip);
+                let bounce = if loop_animation {
+                    bounce_time(time % cycle_duration, duration, bounce_hold, bounce_skip)
+                } else if time < cycle_duration {
+                    bounce_time(time, duration, bounce_hold, bounce_skip)
                 } else {
-                    duration
+                    0.0
+                };
+                if matches!(self, Mode::Bounce) {
+                    bounce
+                } else {
+                    duration - bounce
                 }
             }
         }
     }
+
+    /// Whether a Once playback has reached its final frame at `time`.
+    pub(crate) fn is_finished(&self, time: f64, duration: f32) -> bool {
+        matches!(self, Mode::Once) && time >= duration as f64
+    }
 }
 
 pub(crate) const MODE_FORWARD: &CStr = c"forward";
 pub(crate) const MODE_REVERSE: &CStr = c"reverse";
 pub(crate) const MODE_BOUNCE: &CStr = c"bounce";
 pub(crate) const MODE_REVERSE_BOUNCE: &CStr = c"reverse-bounce";
+pub(crate) const MODE_ONCE: &CStr = c"once";
 
 impl From<&CStr> for Mode {
     fn from(value: &CStr) -> Self {
@@ -87,6 +124,8 @@ impl From<&CStr> for Mode {
             Mode::Bounce
         } else if value == MODE_REVERSE_BOUNCE {
             Mode::ReverseBounce
+        } else if value == MODE_ONCE {
+            Mode::Once
         } else {
             Mode::Forward
         }
@@ -100,6 +139,7 @@ impl From<Mode> for &'static CStr {
             Mode::Reverse => MODE_REVERSE,
             Mode::Bounce => MODE_BOUNCE,
             Mode::ReverseBounce => MODE_REVERSE_BOUNCE,
+            Mode::Once => MODE_ONCE,
         }
     }
 }
@@ -115,13 +155,13 @@ mod tests {
         let mode = Mode::Forward;
 
         // Within duration
-        assert_eq!(mode.next_frame(5.0, DURATION, false), 5.0);
-        assert_eq!(mode.next_frame(0.0, DURATION, false), 0.0);
-        assert_eq!(mode.next_frame(10.0, DURATION, false), 10.0);
+        assert_eq!(mode.next_frame(5.0, DURATION, false, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(0.0, DURATION, false, 0.0, 0.0), 0.0);
+        assert_eq!(mode.next_frame(10.0, DURATION, false, 0.0, 0.0), 10.0);
 
         // Beyond duration
-        assert_eq!(mode.next_frame(15.0, DURATION, false), 10.0);
-        assert_eq!(mode.next_frame(25.0, DURATION, false), 10.0);
+        assert_eq!(mode.next_frame(15.0, DURATION, false, 0.0, 0.0), 10.0);
+        assert_eq!(mode.next_frame(25.0, DURATION, false, 0.0, 0.0), 10.0);
     }
 
     #[test]
@@ -129,14 +169,14 @@ mod tests {
         let mode = Mode::Forward;
 
         // Within duration
-        assert_eq!(mode.next_frame(5.0, DURATION, true), 5.0);
-        assert_eq!(mode.next_frame(0.0, DURATION, true), 0.0);
-        assert_eq!(mode.next_frame(10.0, DURATION, true), 0.0);
+        assert_eq!(mode.next_frame(5.0, DURATION, true, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(0.0, DURATION, true, 0.0, 0.0), 0.0);
+        assert_eq!(mode.next_frame(10.0, DURATION, true, 0.0, 0.0), 0.0);
 
         // Beyond duration - should wrap around
-        assert_eq!(mode.next_frame(15.0, DURATION, true), 5.0);
-        assert_eq!(mode.next_frame(25.0, DURATION, true), 5.0);
-        assert_eq!(mode.next_frame(30.0, DURATION, true), 0.0);
+        assert_eq!(mode.next_frame(15.0, DURATION, true, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(25.0, DURATION, true, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(30.0, DURATION, true, 0.0, 0.0), 0.0);
     }
 
     #[test]
@@ -144,13 +184,13 @@ mod tests {
         let mode = Mode::Reverse;
 
         // Within duration
-        assert_eq!(mode.next_frame(5.0, DURATION, false), 5.0);
-        assert_eq!(mode.next_frame(0.0, DURATION, false), 10.0);
-        assert_eq!(mode.next_frame(10.0, DURATION, false), 0.0);
+        assert_eq!(mode.next_frame(5.0, DURATION, false, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(0.0, DURATION, false, 0.0, 0.0), 10.0);
+        assert_eq!(mode.next_frame(10.0, DURATION, false, 0.0, 0.0), 0.0);
 
         // Beyond duration
-        assert_eq!(mode.next_frame(15.0, DURATION, false), 0.0);
-        assert_eq!(mode.next_frame(25.0, DURATION, false), 0.0);
+        assert_eq!(mode.next_frame(15.0, DURATION, false, 0.0, 0.0), 0.0);
+        assert_eq!(mode.next_frame(25.0, DURATION, false, 0.0, 0.0), 0.0);
     }
 
     #[test]
@@ -158,14 +198,14 @@ mod tests {
         let mode = Mode::Reverse;
 
         // Within duration
-        assert_eq!(mode.next_frame(5.0, DURATION, true), 5.0);
-        assert_eq!(mode.next_frame(0.0, DURATION, true), 10.0);
-        assert_eq!(mode.next_frame(10.0, DURATION, true), 10.0);
+        assert_eq!(mode.next_frame(5.0, DURATION, true, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(0.0, DURATION, true, 0.0, 0.0), 10.0);
+        assert_eq!(mode.next_frame(10.0, DURATION, true, 0.0, 0.0), 10.0);
 
         // Beyond duration - should wrap around
-        assert_eq!(mode.next_frame(15.0, DURATION, true), 5.0);
-        assert_eq!(mode.next_frame(25.0, DURATION, true), 5.0);
-        assert_eq!(mode.next_frame(30.0, DURATION, true), 10.0);
+        assert_eq!(mode.next_frame(15.0, DURATION, true, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(25.0, DURATION, true, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(30.0, DURATION, true, 0.0, 0.0), 10.0);
     }
 
     #[test]
@@ -173,17 +213,17 @@ mod tests {
         let mode = Mode::Bounce;
 
         // First half of bounce cycle (0 -> duration)
-        assert_eq!(mode.next_frame(0.0, DURATION, false), 0.0);
-        assert_eq!(mode.next_frame(5.0, DURATION, false), 5.0);
-        assert_eq!(mode.next_frame(10.0, DURATION, false), 10.0);
+        assert_eq!(mode.next_frame(0.0, DURATION, false, 0.0, 0.0), 0.0);
+        assert_eq!(mode.next_frame(5.0, DURATION, false, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(10.0, DURATION, false, 0.0, 0.0), 10.0);
 
         // Second half of bounce cycle (duration -> 0)
-        assert_eq!(mode.next_frame(15.0, DURATION, false), 5.0);
-        assert_eq!(mode.next_frame(20.0, DURATION, false), 0.0);
+        assert_eq!(mode.next_frame(15.0, DURATION, false, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(20.0, DURATION, false, 0.0, 0.0), 0.0);
 
         // Beyond one complete bounce cycle
-        assert_eq!(mode.next_frame(25.0, DURATION, false), 0.0);
-        assert_eq!(mode.next_frame(30.0, DURATION, false), 0.0);
+        assert_eq!(mode.next_frame(25.0, DURATION, false, 0.0, 0.0), 0.0);
+        assert_eq!(mode.next_frame(30.0, DURATION, false, 0.0, 0.0), 0.0);
     }
 
     #[test]
@@ -191,17 +231,17 @@ mod tests {
         let mode = Mode::Bounce;
 
         // First bounce cycle
-        assert_eq!(mode.next_frame(0.0, DURATION, true), 0.0);
-        assert_eq!(mode.next_frame(5.0, DURATION, true), 5.0);
-        assert_eq!(mode.next_frame(10.0, DURATION, true), 10.0);
-        assert_eq!(mode.next_frame(15.0, DURATION, true), 5.0);
-        assert_eq!(mode.next_frame(20.0, DURATION, true), 0.0);
+        assert_eq!(mode.next_frame(0.0, DURATION, true, 0.0, 0.0), 0.0);
+        assert_eq!(mode.next_frame(5.0, DURATION, true, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(10.0, DURATION, true, 0.0, 0.0), 10.0);
+        assert_eq!(mode.next_frame(15.0, DURATION, true, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(20.0, DURATION, true, 0.0, 0.0), 0.0);
 
         // Second bounce cycle (should repeat)
-        assert_eq!(mode.next_frame(25.0, DURATION, true), 5.0);
-        assert_eq!(mode.next_frame(30.0, DURATION, true), 10.0);
-        assert_eq!(mode.next_frame(35.0, DURATION, true), 5.0);
-        assert_eq!(mode.next_frame(40.0, DURATION, true), 0.0);
+        assert_eq!(mode.next_frame(25.0, DURATION, true, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(30.0, DURATION, true, 0.0, 0.0), 10.0);
+        assert_eq!(mode.next_frame(35.0, DURATION, true, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(40.0, DURATION, true, 0.0, 0.0), 0.0);
     }
 
     #[test]
@@ -209,17 +249,17 @@ mod tests {
         let mode = Mode::ReverseBounce;
 
         // First half of reverse bounce cycle (duration -> 0)
-        assert_eq!(mode.next_frame(0.0, DURATION, false), 10.0);
-        assert_eq!(mode.next_frame(5.0, DURATION, false), 5.0);
-        assert_eq!(mode.next_frame(10.0, DURATION, false), 0.0);
+        assert_eq!(mode.next_frame(0.0, DURATION, false, 0.0, 0.0), 10.0);
+        assert_eq!(mode.next_frame(5.0, DURATION, false, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(10.0, DURATION, false, 0.0, 0.0), 0.0);
 
         // Second half of reverse bounce cycle (0 -> duration)
-        assert_eq!(mode.next_frame(15.0, DURATION, false), 5.0);
-        assert_eq!(mode.next_frame(20.0, DURATION, false), 10.0);
+        assert_eq!(mode.next_frame(15.0, DURATION, false, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(20.0, DURATION, false, 0.0, 0.0), 10.0);
 
         // Beyond one complete reverse bounce cycle
-        assert_eq!(mode.next_frame(25.0, DURATION, false), 10.0);
-        assert_eq!(mode.next_frame(30.0, DURATION, false), 10.0);
+        assert_eq!(mode.next_frame(25.0, DURATION, false, 0.0, 0.0), 10.0);
+        assert_eq!(mode.next_frame(30.0, DURATION, false, 0.0, 0.0), 10.0);
     }
 
     #[test]
@@ -227,17 +267,123 @@ mod tests {
         let mode = Mode::ReverseBounce;
 
         // First reverse bounce cycle
-        assert_eq!(mode.next_frame(0.0, DURATION, true), 10.0);
-        assert_eq!(mode.next_frame(5.0, DURATION, true), 5.0);
-        assert_eq!(mode.next_frame(10.0, DURATION, true), 0.0);
-        assert_eq!(mode.next_frame(15.0, DURATION, true), 5.0);
-        assert_eq!(mode.next_frame(20.0, DURATION, true), 10.0);
+        assert_eq!(mode.next_frame(0.0, DURATION, true, 0.0, 0.0), 10.0);
+        assert_eq!(mode.next_frame(5.0, DURATION, true, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(10.0, DURATION, true, 0.0, 0.0), 0.0);
+        assert_eq!(mode.next_frame(15.0, DURATION, true, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(20.0, DURATION, true, 0.0, 0.0), 10.0);
 
         // Second reverse bounce cycle (should repeat)
-        assert_eq!(mode.next_frame(25.0, DURATION, true), 5.0);
-        assert_eq!(mode.next_frame(30.0, DURATION, true), 0.0);
-        assert_eq!(mode.next_frame(35.0, DURATION, true), 5.0);
-        assert_eq!(mode.next_frame(40.0, DURATION, true), 10.0);
+        assert_eq!(mode.next_frame(25.0, DURATION, true, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(30.0, DURATION, true, 0.0, 0.0), 0.0);
+        assert_eq!(mode.next_frame(35.0, DURATION, true, 0.0, 0.0), 5.0);
+        assert_eq!(mode.next_frame(40.0, DURATION, true, 0.0, 0.0), 10.0);
+    }
+
+    #[test]
+    fn test_once_mode() {
+        let mode = Mode::Once;
+
+        for loop_animation in [false, true] {
+            assert_eq!(
+                mode.next_frame(-1.0, DURATION, loop_animation, 0.0, 0.0),
+                0.0
+            );
+            assert_eq!(
+                mode.next_frame(5.0, DURATION, loop_animation, 0.0, 0.0),
+                5.0
+            );
+
+            // Holds exactly the final frame at and beyond the duration
+            assert_eq!(
+                mode.next_frame(10.0, DURATION, loop_animation, 0.0, 0.0),
+                10.0
+            );
+            assert_eq!(
+                mode.next_frame(15.0, DURATION, loop_animation, 0.0, 0.0),
+                10.0
+            );
+            assert_eq!(
+                mode.next_frame(1.0e9, DURATION, loop_animation, 0.0, 0.0),
+                10.0
+            );
+        }
+    }
+
+    #[test]
+    fn test_once_finished() {
+        assert!(!Mode::Once.is_finished(9.99, DURATION));
+        assert!(Mode::Once.is_finished(10.0, DURATION));
+        assert!(Mode::Once.is_finished(15.0, DURATION));
+        assert!(!Mode::Forward.is_finished(15.0, DURATION));
+    }
+
+    #[test]
+    fn test_bounce_hold_no_loop() {
+        let mode = Mode::Bounce;
+        let hold = 2.0;
+
+        // Hold at start
+        assert_eq!(mode.next_frame(0.0, DURATION, false, hold, 0.0), 0.0);
+        assert_eq!(mode.next_frame(1.0, DURATION, false, hold, 0.0), 0.0);
+
+        // Forward leg
+        assert_eq!(mode.next_frame(2.0, DURATION, false, hold, 0.0), 0.0);
+        assert_eq!(mode.next_frame(7.0, DURATION, false, hold, 0.0), 5.0);
+
+        // Hold at end
+        assert_eq!(mode.next_frame(12.0, DURATION, false, hold, 0.0), 10.0);
+        assert_eq!(mode.next_frame(13.0, DURATION, false, hold, 0.0), 10.0);
+
+        // Backward leg
+        assert_eq!(mode.next_frame(14.0, DURATION, false, hold, 0.0), 10.0);
+        assert_eq!(mode.next_frame(19.0, DURATION, false, hold, 0.0), 5.0);
+        assert_eq!(mode.next_frame(24.0, DURATION, false, hold, 0.0), 0.0);
+
+        // Beyond one complete bounce cycle
+        assert_eq!(mode.next_frame(30.0, DURATION, false, hold, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_bounce_hold_with_loop() {
+        let mode = Mode::Bounce;
+        let hold = 2.0;
+
+        // Second cycle starts at 24 with the start hold
+        assert_eq!(mode.next_frame(25.0, DURATION, true, hold, 0.0), 0.0);
+        assert_eq!(mode.next_frame(31.0, DURATION, true, hold, 0.0), 5.0);
+        assert_eq!(mode.next_frame(37.0, DURATION, true, hold, 0.0), 10.0);
+        assert_eq!(mode.next_frame(43.0, DURATION, true, hold, 0.0), 5.0);
+    }
+
+    #[test]
+    fn test_reverse_bounce_hold() {
+        let mode = Mode::ReverseBounce;
+        let hold = 2.0;
+
+        assert_eq!(mode.next_frame(1.0, DURATION, false, hold, 0.0), 10.0);
+        assert_eq!(mode.next_frame(7.0, DURATION, false, hold, 0.0), 5.0);
+        assert_eq!(mode.next_frame(13.0, DURATION, false, hold, 0.0), 0.0);
+        assert_eq!(mode.next_frame(19.0, DURATION, false, hold, 0.0), 5.0);
+        assert_eq!(mode.next_frame(30.0, DURATION, false, hold, 0.0), 10.0);
+        assert_eq!(mode.next_frame(37.0, DURATION, true, hold, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_large_time_with_loop() {
+        let time = 1.0e12 + 5.0;
+        assert_eq!(
+            Mode::Forward.next_frame(time, DURATION, true, 0.0, 0.0),
+            5.0
+        );
+        assert_eq!(
+            Mode::Reverse.next_frame(time, DURATION, true, 0.0, 0.0),
+            5.0
+        );
+        assert_eq!(
+            Mode::Bounce.next_frame(time + 10.0, DURATION, true, 0.0, 0.0),
+            5.0
+        );
     }
 
     #[test]
@@ -247,11 +393,12 @@ mod tests {
             Mode::Reverse,
             Mode::Bounce,
             Mode::ReverseBounce,
+            Mode::Once,
         ];
 
         for mode in modes {
-            assert_eq!(mode.next_frame(5.0, 0.0, false), 0.0);
-            assert_eq!(mode.next_frame(5.0, 0.0, true), 0.0);
+            assert_eq!(mode.next_frame(5.0, 0.0, false, 0.0, 0.0), 0.0);
+            assert_eq!(mode.next_frame(5.0, 0.0, true, 0.0, 0.0), 0.0);
         }
     }
 
@@ -262,11 +409,12 @@ mod tests {
             Mode::Reverse,
             Mode::Bounce,
             Mode::ReverseBounce,
+            Mode::Once,
         ];
 
         for mode in modes {
-            assert_eq!(mode.next_frame(5.0, -1.0, false), 0.0);
-            assert_eq!(mode.next_frame(5.0, -1.0, true), 0.0);
+            assert_eq!(mode.next_frame(5.0, -1.0, false, 0.0, 0.0), 0.0);
+            assert_eq!(mode.next_frame(5.0, -1.0, true, 0.0, 0.0), 0.0);
         }
     }
 
@@ -279,6 +427,7 @@ mod tests {
             Mode::from(MODE_REVERSE_BOUNCE),
             Mode::ReverseBounce
         ));
+        assert!(matches!(Mode::from(MODE_ONCE), Mode::Once));
 
         // Test unknown mode defaults to Forward
         let unknown = c"unknown";
@@ -291,5 +440,47 @@ mod tests {
         assert_eq!(<&CStr>::from(Mode::Reverse), MODE_REVERSE);
         assert_eq!(<&CStr>::from(Mode::Bounce), MODE_BOUNCE);
         assert_eq!(<&CStr>::from(Mode::ReverseBounce), MODE_REVERSE_BOUNCE);
+        assert_eq!(<&CStr>::from(Mode::Once), MODE_ONCE);
+    }
+
+    #[test]
+    fn test_bounce_skip_endpoints() {
+        let frames = |skip: f32, count: u32| -> Vec<f32> {
+            (0..count)
+                .map(|t| Mode::Bounce.next_frame(t as f64, DURATION, true, 0.0, skip))
+                .collect()
+        };
+        // Without skipping the end frame is shown and its neighbor on both sides
+        assert_eq!(
+            frames(0.0, 21),
+            [
+                0., 1., 2., 3., 4., 5., 6., 7., 8., 9., 10., 9., 8., 7., 6., 5., 4., 3., 2., 1., 0.
+            ]
+        );
+        // Skipping one frame each end turns around without repeating the endpoints
+        assert_eq!(
+            frames(1.0, 19),
+            [
+                0., 1., 2., 3., 4., 5., 6., 7., 8., 9., 8., 7., 6., 5., 4., 3., 2., 1., 0.
+            ]
+        );
+        assert_eq!(Mode::Bounce.cycle_duration(DURATION, 0.0, 1.0), 18.0);
+        assert_eq!(Mode::Forward.cycle_duration(DURATION, 0.0, 1.0), DURATION);
+    }
+
+    #[test]
+    fn test_bounce_skip_endpoints_with_hold() {
+        let frames: Vec<f32> = (0..22)
+            .map(|t| Mode::Bounce.next_frame(t as f64, DURATION, true, 2.0, 1.0))
+            .collect();
+        // The end hold gives up one frame, the return leg resumes at 9
+        assert_eq!(
+            frames,
+            [
+                0., 0., 0., 1., 2., 3., 4., 5., 6., 7., 8., 9., 10., 9., 8., 7., 6., 5., 4., 3.,
+                2., 1.
+            ]
+        );
+        assert_eq!(Mode::Bounce.cycle_duration(DURATION, 2.0, 1.0), 22.0);
     }
 }
@@ -0,0 +1,13 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// ThorVG ABGR8888 packs premultiplied pixels as 0xAABBGGRR,
+// which is RGBA byte order in memory on little-endian hosts.
+
+pub(crate) fn unpack(pixel: u32) -> [u8; 4] {
+    pixel.to_le_bytes()
+}
+
+pub(crate) fn pack(rgba: [u8; 4]) -> u32 {
+    u32::from_le_bytes(rgba)
+}
@@ -0,0 +1,217 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Context;
+use ureq::http::StatusCode;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_SIZE: u64 = 64 * 1024 * 1024;
+
+const ETAG: &str = "etag";
+const LAST_MODIFIED: &str = "last-modified";
+const CONTENT_TYPE: &str = "content-type";
+
+#[derive(Default)]
+pub(crate) struct Response {
+    pub data: Vec<u8>,
+    pub content_type: Option<String>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl Response {
+    fn header(response: &ureq::http::Response<ureq::Body>, name: &str) -> Option<String> {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned)
+    }
+}
+
+/// On-disk cache entry for a URL, stored as a data file plus a metadata
+/// file holding the response headers needed to revalidate it.
+struct CacheEntry {
+    data_path: PathBuf,
+    meta_path: PathBuf,
+}
+
+impl CacheEntry {
+    fn new(url: &str) -> Option<Self> {
+        let dir = cache_dir()?;
+        let key = format!("{:016x}", fnv1a(url.as_bytes()));
+        Some(CacheEntry {
+            data_path: dir.join(format!("{key}.data")),
+            meta_path: dir.join(format!("{key}.meta")),
+        })
+    }
+
+    fn age(&self) -> Option<Duration> {
+        let modified = std::fs::metadata(&self.meta_path).ok()?.modified().ok()?;
+        SystemTime::now().duration_since(modified).ok()
+    }
+
+    fn load(&self) -> Option<Response> {
+        let data = std::fs::read(&self.data_path).ok()?;
+        let meta = std::fs::read_to_string(&self.meta_path).ok()?;
+        let mut response = Response {
+            data,
+            ..Default::default()
+        };
+        for line in meta.lines() {
+            let Some((name, value)) = line.split_once(": ") else {
+                continue;
+            };
+            let value = Some(value.to_owned());
+            match name {
+                ETAG => response.etag = value,
+                LAST_MODIFIED => response.last_modified = value,
+                CONTENT_TYPE => response.content_type = value,
+                _ => {}
+            }
+        }
+        Some(response)
+    }
+
+    fn store_meta(&self, response: &Response) -> std::io::Result<()> {
+        let mut meta = String::new();
+        for (name, value) in [
+            (ETAG, &response.etag),
+            (LAST_MODIFIED, &response.last_modified),
+            (CONTENT_TYPE, &response.content_type),
+        ] {
+            if let Some(value) = value {
+                meta.push_str(&format!("{name}: {value}\n"));
+            }
+        }
+        std::fs::write(&self.meta_path, meta)
+    }
+
+    fn store(&self, response: &Response) -> std::io::Result<()> {
+        if let Some(dir) = self.data_path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&self.data_path, &response.data)?;
+        self.store_meta(response)
+    }
+}
+
+fn cache_dir() -> Option<PathBuf> {
+    let env_dir = |name| std::env::var_os(name).filter(|dir| !dir.is_empty());
+    let base = if cfg!(target_os = "windows") {
+        env_dir("LOCALAPPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME").map(|home| PathBuf::from(home).join("Library/Caches"))
+    } else {
+        env_dir("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env_dir("HOME").map(|home| PathBuf::from(home).join(".cache")))
+    };
+    base.map(|base| base.join("l0ttie"))
+}
+
+// Stable across Rust releases, unlike DefaultHasher
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn request(url: &str, cached: Option<&Response>) -> anyhow::Result<Option<Response>> {
+    let mut request = ureq::get(url);
+    if let Some(cached) = cached {
+        if let Some(etag) = &cached.etag {
+            request = request.header("If-None-Match", etag);
+        }
+        if let Some(last_modified) = &cached.last_modified {
+            request = request.header("If-Modified-Since", last_modified);
+        }
+    }
+    let mut response = request
+        .config()
+        .timeout_global(Some(TIMEOUT))
+        .build()
+        .call()
+        .with_context(|| format!("Failed to load lottie animation url: {url}"))?;
+    if cached.is_some() && response.status() == StatusCode::NOT_MODIFIED {
+        return Ok(None);
+    }
+    let content_type = Response::header(&response, CONTENT_TYPE);
+    let etag = Response::header(&response, ETAG);
+    let last_modified = Response::header(&response, LAST_MODIFIED);
+    let data = response
+        .body_mut()
+        .with_config()
+        .limit(MAX_SIZE)
+        .read_to_vec()
+        .with_context(|| format!("Failed to read lottie animation url: {url}"))?;
+    Ok(Some(Response {
+        data,
+        content_type,
+        etag,
+        last_modified,
+    }))
+}
+
+/// Fetch `url`, serving it from the on-disk cache if it was fetched less than
+/// `cache_ttl` ago, and falling back to the cache if the server is unreachable.
+/// Also returns warnings for cache failures and fallbacks.
+pub(crate) fn fetch(url: &str, cache_ttl: Duration) -> anyhow::Result<(Response, Vec<String>)> {
+    let mut warnings = Vec::new();
+    let Some(entry) = CacheEntry::new(url) else {
+        return Ok((request(url, None)?.unwrap_or_default(), warnings));
+    };
+    let cached = entry.load();
+    if let Some(cached) = cached {
+        if entry.age().is_some_and(|age| age < cache_ttl) {
+            return Ok((cached, warnings));
+        }
+        let response = match request(url, Some(&cached)) {
+            Ok(Some(response)) => {
+                if let Err(err) = entry.store(&response) {
+                    warnings.push(format!(
+                        "Failed to cache lottie animation url {url}: {err:?}"
+                    ));
+                }
+                response
+            }
+            Ok(None) => {
+                // Not modified, refresh the cache timestamp
+                if let Err(err) = entry.store_meta(&cached) {
+                    warnings.push(format!(
+                        "Failed to cache lottie animation url {url}: {err:?}"
+                    ));
+                }
+                cached
+            }
+            Err(err) => {
+                warnings.push(format!("Using cached lottie animation url {url}: {err:?}"));
+                cached
+            }
+        };
+        return Ok((response, warnings));
+    }
+    let response = request(url, None)?.unwrap_or_default();
+    if let Err(err) = entry.store(&response) {
+        warnings.push(format!(
+            "Failed to cache lottie animation url {url}: {err:?}"
+        ));
+    }
+    Ok((response, warnings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+    }
+}
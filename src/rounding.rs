@@ -0,0 +1,120 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::ffi::CStr;
+
+/// How fractional frame numbers are converted before being set on the animation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum FrameRounding {
+    // Pass fractional frames through to ThorVG
+    None,
+    Floor,
+    Round,
+    Ceil,
+}
+
+impl FrameRounding {
+    pub(crate) fn apply(&self, frame: f32) -> f32 {
+        match self {
+            FrameRounding::None => frame,
+            FrameRounding::Floor => frame.floor(),
+            FrameRounding::Round => frame.round(),
+            FrameRounding::Ceil => frame.ceil(),
+        }
+    }
+}
+
+/// Quantize `frame` to the nearest multiple of `step` after `start`, clamped to the
+/// last multiple within the `frames` long range from `start`. Unchanged if `step` <= 1.
+pub(crate) fn step(frame: f32, step: f32, start: f32, frames: f32) -> f32 {
+    if step > 1.0 {
+        (start + ((frame - start) / step).round() * step)
+            .min(start + (frames / step).floor() * step)
+            .max(start)
+    } else {
+        frame
+    }
+}
+
+pub(crate) const ROUNDING_NONE: &CStr = c"none";
+pub(crate) const ROUNDING_FLOOR: &CStr = c"floor";
+pub(crate) const ROUNDING_ROUND: &CStr = c"round";
+pub(crate) const ROUNDING_CEIL: &CStr = c"ceil";
+
+impl From<&CStr> for FrameRounding {
+    fn from(value: &CStr) -> Self {
+        if value == ROUNDING_FLOOR {
+            FrameRounding::Floor
+        } else if value == ROUNDING_ROUND {
+            FrameRounding::Round
+        } else if value == ROUNDING_CEIL {
+            FrameRounding::Ceil
+        } else {
+            FrameRounding::None
+        }
+    }
+}
+
+impl From<FrameRounding> for &'static CStr {
+    fn from(rounding: FrameRounding) -> Self {
+        match rounding {
+            FrameRounding::None => ROUNDING_NONE,
+            FrameRounding::Floor => ROUNDING_FLOOR,
+            FrameRounding::Round => ROUNDING_ROUND,
+            FrameRounding::Ceil => ROUNDING_CEIL,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_none() {
+        assert_eq!(FrameRounding::None.apply(2.4), 2.4);
+    }
+
+    #[test]
+    fn test_floor() {
+        assert_eq!(FrameRounding::Floor.apply(2.4), 2.0);
+        assert_eq!(FrameRounding::Floor.apply(2.6), 2.0);
+    }
+
+    #[test]
+    fn test_round() {
+        assert_eq!(FrameRounding::Round.apply(2.4), 2.0);
+        assert_eq!(FrameRounding::Round.apply(2.5), 3.0);
+    }
+
+    #[test]
+    fn test_ceil() {
+        assert_eq!(FrameRounding::Ceil.apply(2.4), 3.0);
+        assert_eq!(FrameRounding::Ceil.apply(2.0), 2.0);
+    }
+
+    #[test]
+    fn test_rounding_from_cstr() {
+        assert_eq!(FrameRounding::from(ROUNDING_NONE), FrameRounding::None);
+        assert_eq!(FrameRounding::from(ROUNDING_FLOOR), FrameRounding::Floor);
+        assert_eq!(FrameRounding::from(ROUNDING_ROUND), FrameRounding::Round);
+        assert_eq!(FrameRounding::from(ROUNDING_CEIL), FrameRounding::Ceil);
+        assert_eq!(FrameRounding::from(c"unknown"), FrameRounding::None);
+    }
+
+    #[test]
+    fn test_step() {
+        assert_eq!(step(12.0, 5.0, 0.0, 60.0), 10.0);
+        assert_eq!(step(12.5, 5.0, 0.0, 60.0), 15.0);
+        assert_eq!(step(12.3, 1.0, 0.0, 60.0), 12.3);
+        assert_eq!(step(12.3, 0.0, 0.0, 60.0), 12.3);
+        // Relative to the range start and clamped to its end
+        assert_eq!(step(24.0, 5.0, 12.0, 30.0), 22.0);
+        // 7 doesn't divide 60, so the end clamps to the last multiple
+        assert_eq!(step(60.0, 7.0, 0.0, 60.0), 56.0);
+        assert_eq!(step(59.9, 7.0, 0.0, 60.0), 56.0);
+        assert_eq!(step(40.0, 7.0, 12.0, 30.0), 40.0);
+        assert_eq!(step(41.0, 7.0, 12.0, 30.0), 40.0);
+        assert_eq!(step(41.0, 5.0, 12.0, 30.0), 42.0);
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::pixel;
+
+/// Times of `samples` evenly spaced samples over a shutter open for
+/// `shutter` (0..1) of `frame_duration`, starting at `time`.
+pub(crate) fn sample_times(
+    time: f64,
+    shutter: f64,
+    frame_duration: f64,
+    samples: usize,
+) -> impl Iterator<Item = f64> {
+    let window = shutter.clamp(0.0, 1.0) * frame_duration;
+    let samples = samples.max(1);
+    (0..samples).map(move |i| time + window * i as f64 / samples as f64)
+}
+
+/// Add the channels of `frame` to `accum`, a buffer of 4 sums per pixel.
+pub(crate) fn accumulate(accum: &mut [u32], frame: &[u32]) {
+    for (sums, pixel) in accum.chunks_exact_mut(4).zip(frame) {
+        for (sum, channel) in sums.iter_mut().zip(pixel::unpack(*pixel)) {
+            *sum += channel as u32;
+        }
+    }
+}
+
+/// Write the average of `samples` accumulated frames into `frame`.
+pub(crate) fn average(accum: &[u32], samples: usize, frame: &mut [u32]) {
+    let samples = samples.max(1) as u32;
+    for (pixel, sums) in frame.iter_mut().zip(accum.chunks_exact(4)) {
+        *pixel = pixel::pack(std::array::from_fn(|i| {
+            ((sums[i] + samples / 2) / samples) as u8
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_times() {
+        assert_eq!(sample_times(1.0, 0.5, 0.1, 1).collect::<Vec<_>>(), [1.0]);
+        assert_eq!(
+            sample_times(0.0, 0.5, 1.0, 4).collect::<Vec<_>>(),
+            [0.0, 0.125, 0.25, 0.375]
+        );
+    }
+
+    #[test]
+    fn test_single_sample_unchanged() {
+        let frame = [
+            pixel::pack([10, 20, 30, 40]),
+            pixel::pack([255, 255, 255, 255]),
+        ];
+        let mut accum = [0; 8];
+        accumulate(&mut accum, &frame);
+        let mut out = [0; 2];
+        average(&accum, 1, &mut out);
+        assert_eq!(out, frame);
+    }
+
+    #[test]
+    fn test_average() {
+        let mut accum = [0; 4];
+        accumulate(&mut accum, &[pixel::pack([0, 100, 255, 255])]);
+        accumulate(&mut accum, &[pixel::pack([0, 0, 0, 0])]);
+        let mut out = [0];
+        average(&accum, 2, &mut out);
+        assert_eq!(pixel::unpack(out[0]), [0, 50, 128, 128]);
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::{path::Path, time::Duration};
+
+use anyhow::Context;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Format {
+    Json,
+    DotLottie,
+}
+
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+impl Format {
+    fn detect(path: &str, content_type: Option<&str>, data: &[u8]) -> Self {
+        let content_type = content_type
+            .and_then(|content_type| content_type.split(';').next())
+            .map(|content_type| content_type.trim().to_ascii_lowercase());
+        match content_type.as_deref() {
+            Some("application/json") => return Format::Json,
+            Some("application/zip" | "application/zip+dotlottie") => return Format::DotLottie,
+            _ => {}
+        }
+        let path = path.split(['?', '#']).next().unwrap_or(path);
+        if path.to_ascii_lowercase().ends_with(".lottie") || data.starts_with(ZIP_MAGIC) {
+            Format::DotLottie
+        } else {
+            Format::Json
+        }
+    }
+}
+
+pub(crate) struct Source {
+    pub data: Vec<u8>,
+    pub format: Format,
+}
+
+pub(crate) fn is_url(path: &str) -> bool {
+    path.split_once("://").is_some_and(|(scheme, _)| {
+        scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https")
+    })
+}
+
+/// Resolve a relative file `path` against `base_dir`, URLs and absolute paths
+/// are unchanged. An empty `base_dir` leaves it relative to the working directory.
+pub(crate) fn resolve(path: &str, base_dir: &str) -> String {
+    if base_dir.is_empty() || is_url(path) || Path::new(path).is_absolute() {
+        path.to_owned()
+    } else {
+        Path::new(base_dir)
+            .join(path)
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+/// How often a failed read is retried, for flaky network storage.
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct Retry {
+    pub retries: u32,
+    pub delay: Duration,
+}
+
+impl Retry {
+    // Call `f` until it succeeds or the retries are exhausted, sleeping in between
+    fn run<T>(&self, mut f: impl FnMut() -> anyhow::Result<T>) -> anyhow::Result<T> {
+        let mut attempt = 0;
+        loop {
+            match f() {
+                Err(_) if attempt < self.retries => {
+                    attempt += 1;
+                    std::thread::sleep(self.delay);
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Read the file or url at `path`, also returning warnings for url cache problems.
+pub(crate) fn load(
+    path: &str,
+    cache_ttl: Duration,
+    retry: Retry,
+) -> anyhow::Result<(Source, Vec<String>)> {
+    retry.run(|| load_once(path, cache_ttl))
+}
+
+fn load_once(path: &str, cache_ttl: Duration) -> anyhow::Result<(Source, Vec<String>)> {
+    if is_url(path) {
+        load_url(path, cache_ttl)
+    } else {
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read lottie animation path: {path}"))?;
+        let format = Format::detect(path, None, &data);
+        Ok((Source { data, format }, Vec::new()))
+    }
+}
+
+#[cfg(feature = "remote")]
+fn load_url(url: &str, cache_ttl: Duration) -> anyhow::Result<(Source, Vec<String>)> {
+    let (response, warnings) = crate::remote::fetch(url, cache_ttl)?;
+    let format = Format::detect(url, response.content_type.as_deref(), &response.data);
+    let source = Source {
+        data: response.data,
+        format,
+    };
+    Ok((source, warnings))
+}
+
+#[cfg(not(feature = "remote"))]
+fn load_url(url: &str, _cache_ttl: Duration) -> anyhow::Result<(Source, Vec<String>)> {
+    anyhow::bail!("Loading lottie animation urls requires the 'remote' feature: {url}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_url() {
+        assert!(is_url("http://example.com/a.json"));
+        assert!(is_url("HTTPS://example.com/a.json"));
+        assert!(!is_url("/tmp/a.json"));
+        assert!(!is_url("C:\\tmp\\a.json"));
+        assert!(!is_url("file:///tmp/a.json"));
+    }
+
+    #[test]
+    fn test_resolve() {
+        let base = std::env::temp_dir();
+        let base_dir = base.to_str().unwrap();
+        assert_eq!(
+            resolve("b.json", base_dir),
+            base.join("b.json").to_str().unwrap()
+        );
+        assert_eq!(resolve("a/b.json", ""), "a/b.json");
+        let absolute = base.join("b.json");
+        let absolute = absolute.to_str().unwrap();
+        assert_eq!(resolve(absolute, "elsewhere"), absolute);
+        assert_eq!(
+            resolve("https://example.com/b.json", "/projects/x"),
+            "https://example.com/b.json"
+        );
+    }
+
+    #[test]
+    fn test_retry() {
+        let retry = Retry {
+            retries: 2,
+            delay: Duration::ZERO,
+        };
+        let mut calls = 0;
+        let result = retry.run(|| {
+            calls += 1;
+            anyhow::ensure!(calls == 3, "attempt {calls}");
+            Ok(calls)
+        });
+        assert_eq!(result.unwrap(), 3);
+
+        let mut calls = 0;
+        let result: anyhow::Result<()> = retry.run(|| {
+            calls += 1;
+            anyhow::bail!("attempt {calls}")
+        });
+        assert_eq!(result.unwrap_err().to_string(), "attempt 3");
+    }
+
+    #[test]
+    fn test_detect_content_type() {
+        assert_eq!(
+            Format::detect("a.lottie", Some("application/json; charset=utf-8"), b"{}"),
+            Format::Json
+        );
+        assert_eq!(
+            Format::detect("a.json", Some("application/zip"), b"{}"),
+            Format::DotLottie
+        );
+    }
+
+    #[test]
+    fn test_detect_extension() {
+        assert_eq!(Format::detect("a.json", None, b"{}"), Format::Json);
+        assert_eq!(Format::detect("a.LOTTIE", None, b""), Format::DotLottie);
+        assert_eq!(
+            Format::detect("http://example.com/a.lottie?v=1", Some("text/plain"), b""),
+            Format::DotLottie
+        );
+    }
+
+    #[test]
+    fn test_detect_magic() {
+        assert_eq!(
+            Format::detect("animation", None, b"PK\x03\x04rest"),
+            Format::DotLottie
+        );
+    }
+}
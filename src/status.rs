@@ -0,0 +1,45 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/// Plugin health, exposed to hosts as the `status` number.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Status {
+    Uninitialized = 0,
+    // Loading, or loaded with no frame rendered yet
+    Loading = 1,
+    Rendering = 2,
+    LoadFailed = 3,
+    RenderFailed = 4,
+}
+
+impl Status {
+    /// Status after a render call, given whether a load was attempted
+    /// and succeeded and whether the frame rendered cleanly.
+    pub(crate) fn after_render(initialized: bool, loaded: bool, rendered: bool) -> Self {
+        match (initialized, loaded, rendered) {
+            (false, _, _) => Status::Uninitialized,
+            (true, false, _) => Status::LoadFailed,
+            (true, true, true) => Status::Rendering,
+            (true, true, false) => Status::RenderFailed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_after_render() {
+        assert_eq!(
+            Status::after_render(false, false, false),
+            Status::Uninitialized
+        );
+        assert_eq!(Status::after_render(true, false, true), Status::LoadFailed);
+        assert_eq!(Status::after_render(true, true, true), Status::Rendering);
+        assert_eq!(
+            Status::after_render(true, true, false),
+            Status::RenderFailed
+        );
+    }
+}
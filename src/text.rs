@@ -0,0 +1,148 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use anyhow::Context;
+use serde_json::Value;
+
+const TEXT_LAYER: u64 = 5;
+
+/// Parse `key=value;key2=value2` replacements, skipping empty entries.
+pub(crate) fn parse(spec: &str) -> anyhow::Result<Vec<(&str, &str)>> {
+    spec.split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            entry
+                .split_once('=')
+                .map(|(key, value)| (key.trim(), value.trim()))
+                .with_context(|| format!("Invalid text replacement, expected key=value: {entry}"))
+        })
+        .collect()
+}
+
+// Set the text of every keyframe of a text layer's document
+fn replace_layer(layer: &mut Value, text: &str) {
+    let Some(keyframes) = layer
+        .get_mut("t")
+        .and_then(|t| t.get_mut("d"))
+        .and_then(|d| d.get_mut("k"))
+        .and_then(Value::as_array_mut)
+    else {
+        return;
+    };
+    for keyframe in keyframes {
+        if let Some(document) = keyframe.get_mut("s").and_then(Value::as_object_mut) {
+            document.insert("t".to_owned(), Value::String(text.to_owned()));
+        }
+    }
+}
+
+fn replace_layers(layers: &mut [Value], replacements: &[(&str, &str)], matched: &mut [bool]) {
+    for layer in layers {
+        if layer.get("ty").and_then(Value::as_u64) != Some(TEXT_LAYER) {
+            continue;
+        }
+        let Some(name) = layer.get("nm").and_then(Value::as_str) else {
+            continue;
+        };
+        if let Some(index) = replacements.iter().position(|(key, _)| *key == name) {
+            matched[index] = true;
+            replace_layer(layer, replacements[index].1);
+        }
+    }
+}
+
+/// Replace the text of text layers named by `replacements` in the Lottie JSON `data`,
+/// including layers in precomp assets.
+/// Returns the updated JSON and the keys that did not match any text layer.
+pub(crate) fn replace<'a>(
+    data: &str,
+    replacements: &[(&'a str, &str)],
+) -> anyhow::Result<(String, Vec<&'a str>)> {
+    let mut json: Value =
+        serde_json::from_str(data).context("Invalid lottie JSON for text replacement")?;
+    let mut matched = vec![false; replacements.len()];
+    if let Some(layers) = json.get_mut("layers").and_then(Value::as_array_mut) {
+        replace_layers(layers, replacements, &mut matched);
+    }
+    if let Some(assets) = json.get_mut("assets").and_then(Value::as_array_mut) {
+        for asset in assets {
+            if let Some(layers) = asset.get_mut("layers").and_then(Value::as_array_mut) {
+                replace_layers(layers, replacements, &mut matched);
+            }
+        }
+    }
+    let unknown = replacements
+        .iter()
+        .zip(matched)
+        .filter(|(_, matched)| !matched)
+        .map(|((key, _), _)| *key)
+        .collect();
+    let data = serde_json::to_string(&json).context("Failed to serialize lottie JSON")?;
+    Ok((data, unknown))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = r#"{"layers":[
+        {"ty":5,"nm":"name","t":{"d":{"k":[{"s":{"t":"NAME","s":24},"t":0}]}}},
+        {"ty":4,"nm":"title"}
+    ],"assets":[{"id":"comp","layers":[
+        {"ty":5,"nm":"title","t":{"d":{"k":[{"s":{"t":"TITLE"},"t":0},{"s":{"t":"TITLE"},"t":30}]}}}
+    ]}]}"#;
+
+    fn texts(data: &str, pointer: &str) -> Vec<String> {
+        let json: Value = serde_json::from_str(data).unwrap();
+        json.pointer(pointer)
+            .and_then(Value::as_array)
+            .unwrap()
+            .iter()
+            .map(|keyframe| {
+                keyframe
+                    .pointer("/s/t")
+                    .and_then(Value::as_str)
+                    .unwrap()
+                    .to_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("name=Jane Doe; title = Reporter;;").unwrap(),
+            [("name", "Jane Doe"), ("title", "Reporter")]
+        );
+        assert_eq!(parse("").unwrap(), []);
+        assert!(parse("name").is_err());
+    }
+
+    #[test]
+    fn test_replace() {
+        let (data, unknown) = replace(
+            DATA,
+            &[
+                ("name", "Jane Doe"),
+                ("title", "Reporter"),
+                ("missing", "x"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(unknown, ["missing"]);
+        assert_eq!(texts(&data, "/layers/0/t/d/k"), ["Jane Doe"]);
+        assert_eq!(
+            texts(&data, "/assets/0/layers/0/t/d/k"),
+            ["Reporter", "Reporter"]
+        );
+    }
+
+    #[test]
+    fn test_replace_ignores_non_text_layers() {
+        let (data, unknown) =
+            replace(r#"{"layers":[{"ty":4,"nm":"name"}]}"#, &[("name", "x")]).unwrap();
+        assert_eq!(unknown, ["name"]);
+        assert!(!data.contains("\"x\""));
+    }
+}
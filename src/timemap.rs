@@ -0,0 +1,73 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use anyhow::Context;
+
+/// Table of (host time, animation time) pairs sorted by host time.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct TimeMap(Vec<(f64, f64)>);
+
+impl TimeMap {
+    /// Parse `t0:a0,t1:a1,...` pairs, skipping empty entries.
+    pub(crate) fn parse(spec: &str) -> anyhow::Result<Self> {
+        let mut pairs = spec
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (host, animation) = entry
+                    .split_once(':')
+                    .with_context(|| format!("Invalid time_map entry, expected t:a: {entry}"))?;
+                let parse = |value: &str| {
+                    value
+                        .trim()
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|value| value.is_finite())
+                        .with_context(|| format!("Invalid time_map time: {value}"))
+                };
+                Ok((parse(host)?, parse(animation)?))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        anyhow::ensure!(!pairs.is_empty(), "time_map has no entries");
+        pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Ok(TimeMap(pairs))
+    }
+
+    /// Animation time at host `time`, interpolated linearly between entries
+    /// and clamped to the first and last.
+    pub(crate) fn time(&self, time: f64) -> f64 {
+        let index = self.0.partition_point(|(host, _)| *host <= time);
+        match (index.checked_sub(1).map(|i| self.0[i]), self.0.get(index)) {
+            (Some((t0, a0)), Some(&(t1, a1))) => a0 + (time - t0) / (t1 - t0) * (a1 - a0),
+            (Some((_, a0)), None) => a0,
+            (None, Some(&(_, a1))) => a1,
+            (None, None) => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let map = TimeMap::parse(" 2:1, 0:0 ,,4:4").unwrap();
+        assert_eq!(map, TimeMap(vec![(0.0, 0.0), (2.0, 1.0), (4.0, 4.0)]));
+        assert!(TimeMap::parse("").is_err());
+        assert!(TimeMap::parse("1:2,3").is_err());
+        assert!(TimeMap::parse("1:x").is_err());
+    }
+
+    #[test]
+    fn test_time() {
+        let map = TimeMap::parse("0:0,2:1,4:4").unwrap();
+        assert_eq!(map.time(-1.0), 0.0);
+        assert_eq!(map.time(1.0), 0.5);
+        assert_eq!(map.time(2.0), 1.0);
+        assert_eq!(map.time(3.0), 2.5);
+        assert_eq!(map.time(10.0), 4.0);
+        assert_eq!(TimeMap::parse("1:5").unwrap().time(0.0), 5.0);
+    }
+}
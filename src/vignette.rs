@@ -0,0 +1,88 @@
+// Copyright (C) 2025 Andrew Wason
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::pixel;
+
+/// Radial falloff map darkening the frame edges, rebuilt only when the
+/// frame size or vignette parameters change.
+#[derive(Debug, Default)]
+pub(crate) struct Vignette {
+    // Width, height and the bits of strength and radius the map was built for
+    key: Option<(usize, usize, u32, u32)>,
+    // RGB scale per pixel, 256 is unchanged
+    map: Vec<u16>,
+}
+
+impl Vignette {
+    /// Build the map for a `width` x `height` frame. `strength` (0-1) is the
+    /// darkening at the corners, `radius` (0-1 of the center to corner distance)
+    /// is where the falloff begins.
+    pub(crate) fn prepare(&mut self, width: usize, height: usize, strength: f32, radius: f32) {
+        let key = (width, height, strength.to_bits(), radius.to_bits());
+        if self.key == Some(key) {
+            return;
+        }
+        self.key = Some(key);
+        let strength = strength.clamp(0.0, 1.0);
+        let radius = radius.clamp(0.0, 1.0);
+        let half = [width as f32 / 2.0, height as f32 / 2.0];
+        let corner = half[0].hypot(half[1]).max(f32::EPSILON);
+        self.map.clear();
+        self.map.extend((0..width * height).map(|i| {
+            let (x, y) = ((i % width) as f32 + 0.5, (i / width) as f32 + 0.5);
+            let distance = (x - half[0]).hypot(y - half[1]) / corner;
+            let t = ((distance - radius) / (1.0 - radius).max(f32::EPSILON)).clamp(0.0, 1.0);
+            // Smoothstep so the falloff starts without a visible edge
+            let falloff = t * t * (3.0 - 2.0 * t);
+            ((1.0 - strength * falloff) * 256.0).round() as u16
+        }));
+    }
+
+    /// Scale the premultiplied RGB of `frame` by the prepared map, preserving alpha.
+    pub(crate) fn apply(&self, frame: &mut [u32]) {
+        if self.map.len() != frame.len() {
+            return;
+        }
+        for (pixel, scale) in frame.iter_mut().zip(&self.map) {
+            if *scale >= 256 {
+                continue;
+            }
+            let [r, g, b, a] = pixel::unpack(*pixel);
+            let scale = |c: u8| ((c as u32 * *scale as u32 + 128) >> 8) as u8;
+            *pixel = pixel::pack([scale(r), scale(g), scale(b), a]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vignette() {
+        let (width, height) = (16, 8);
+        let mut vignette = Vignette::default();
+        vignette.prepare(width, height, 1.0, 0.5);
+        let mut frame = vec![pixel::pack([200, 100, 50, 255]); width * height];
+        vignette.apply(&mut frame);
+        // Center unchanged, corners darkened with alpha preserved
+        assert_eq!(pixel::unpack(frame[4 * width + 8]), [200, 100, 50, 255]);
+        let corner = pixel::unpack(frame[0]);
+        assert!(corner[0] < 50 && corner[3] == 255, "{corner:?}");
+    }
+
+    #[test]
+    fn test_vignette_cached() {
+        let mut vignette = Vignette::default();
+        vignette.prepare(4, 4, 0.5, 0.0);
+        let map = vignette.map.as_ptr();
+        vignette.prepare(4, 4, 0.5, 0.0);
+        assert_eq!(vignette.map.as_ptr(), map);
+        vignette.prepare(2, 2, 0.5, 0.0);
+        assert_eq!(vignette.map.len(), 4);
+        // Size mismatch leaves the frame untouched
+        let mut frame = [0xffffffff; 9];
+        vignette.apply(&mut frame);
+        assert_eq!(frame, [0xffffffff; 9]);
+    }
+}